@@ -7,7 +7,7 @@ pub fn part_1() {
     let reader = BufReader::new(file);
     let result: u64 = reader
         .lines()
-        .filter_map(|l| l.ok())
+        .map_while(Result::ok)
         .filter_map(|s| s.parse::<u64>().ok())
         .map(fuel_for_mass)
         .sum();
@@ -20,7 +20,7 @@ pub fn part_2() {
     let reader = BufReader::new(file);
     let result: u64 = reader
         .lines()
-        .filter_map(|l| l.ok())
+        .map_while(Result::ok)
         .filter_map(|s| s.parse::<u64>().ok())
         .map(fuel_for_mass_with_extra_fuel)
         .sum();
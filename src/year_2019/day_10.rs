@@ -0,0 +1,234 @@
+use crate::year_2019::point::Point;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_10_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let asteroids = parse_asteroids(&input);
+    let (station, visible) = best_station(&asteroids).expect("Map has no asteroids");
+    println!(
+        "Best station at {},{} sees {} asteroids",
+        station.x, station.y, visible
+    );
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_10_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let asteroids = parse_asteroids(&input);
+    let (station, _) = best_station(&asteroids).expect("Map has no asteroids");
+    let order = vaporisation_order(station, &asteroids);
+    let target = order.get(199).expect("Fewer than 200 asteroids");
+    println!("200th vaporised asteroid: {}", target.x * 100 + target.y);
+}
+
+/// Positions of the asteroids, with x growing to the right and y growing
+/// downwards. A station marked with `X` is an asteroid as well.
+fn parse_asteroids(input: &str) -> Vec<Point> {
+    input
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '#' || *c == 'X')
+                .map(move |(x, _)| Point::new(x as i64, y as i64))
+        })
+        .collect()
+}
+
+/// Asteroids that share a reduced direction are on one line of sight, so
+/// only the closest of them is visible.
+fn count_visible(station: Point, asteroids: &[Point]) -> usize {
+    asteroids
+        .iter()
+        .filter(|&&asteroid| asteroid != station)
+        .map(|&asteroid| (asteroid - station).reduced())
+        .collect::<HashSet<Point>>()
+        .len()
+}
+
+fn best_station(asteroids: &[Point]) -> Option<(Point, usize)> {
+    asteroids
+        .iter()
+        .map(|&station| (station, count_visible(station, asteroids)))
+        .max_by_key(|(_, visible)| *visible)
+}
+
+/// Orders two directions clockwise, starting straight up. The map has y
+/// growing downwards, so up is a negative y.
+fn clockwise(a: Point, b: Point) -> Ordering {
+    let half = |p: Point| {
+        if 0 < p.x || (p.x == 0 && p.y < 0) {
+            0
+        } else {
+            1
+        }
+    };
+    half(a)
+        .cmp(&half(b))
+        .then_with(|| (b.x * a.y - b.y * a.x).cmp(&0))
+}
+
+/// Every asteroid other than the station in the order a laser vaporises
+/// them. The laser starts pointing up, turns clockwise, and hits only the
+/// closest remaining asteroid in each direction on every rotation.
+fn vaporisation_order(station: Point, asteroids: &[Point]) -> Vec<Point> {
+    let mut lines: HashMap<Point, Vec<Point>> = HashMap::new();
+    for &asteroid in asteroids.iter().filter(|&&a| a != station) {
+        let offset = asteroid - station;
+        lines.entry(offset.reduced()).or_default().push(offset);
+    }
+    let mut targets: Vec<(usize, Point, Point)> = vec![];
+    for (direction, mut offsets) in lines {
+        offsets.sort_by_key(|offset| offset.manhattan());
+        targets.extend(
+            offsets
+                .into_iter()
+                .enumerate()
+                .map(|(rotation, offset)| (rotation, direction, station + offset)),
+        );
+    }
+    targets.sort_by(
+        |(rotation_a, direction_a, _), (rotation_b, direction_b, _)| {
+            rotation_a
+                .cmp(rotation_b)
+                .then_with(|| clockwise(*direction_a, *direction_b))
+        },
+    );
+    targets
+        .into_iter()
+        .map(|(_, _, asteroid)| asteroid)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LARGE_EXAMPLE: &str = ".#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##";
+
+    #[test]
+    fn example_10_1() {
+        let asteroids = parse_asteroids(".#..#\n.....\n#####\n....#\n...##");
+        assert_eq!(best_station(&asteroids), Some((Point::new(3, 4), 8)));
+        assert_eq!(count_visible(Point::new(1, 0), &asteroids), 7);
+        assert_eq!(count_visible(Point::new(4, 2), &asteroids), 5);
+    }
+
+    #[test]
+    fn example_10_2() {
+        let asteroids = parse_asteroids(
+            "......#.#.
+#..#.#....
+..#######.
+.#.#.###..
+.#..#.....
+..#....#.#
+#..#....#.
+.##.#..###
+##...#..#.
+.#....####",
+        );
+        assert_eq!(best_station(&asteroids), Some((Point::new(5, 8), 33)));
+    }
+
+    #[test]
+    fn example_10_3() {
+        let asteroids = parse_asteroids(LARGE_EXAMPLE);
+        assert_eq!(best_station(&asteroids), Some((Point::new(11, 13), 210)));
+    }
+
+    #[test]
+    fn example_10_4() {
+        let asteroids = parse_asteroids(
+            ".#....#####...#..
+##...##.#####..##
+##...#...#.#####.
+..#.....X...###..
+..#.#.....#....##",
+        );
+        let order = vaporisation_order(Point::new(8, 3), &asteroids);
+        let expected: Vec<Point> = [
+            (8, 1),
+            (9, 0),
+            (9, 1),
+            (10, 0),
+            (9, 2),
+            (11, 1),
+            (12, 1),
+            (11, 2),
+            (15, 1),
+        ]
+        .iter()
+        .map(|&(x, y)| Point::new(x, y))
+        .collect();
+        assert_eq!(order[..9], expected[..]);
+        assert_eq!(order.len(), asteroids.len() - 1);
+    }
+
+    #[test]
+    fn example_10_5() {
+        let asteroids = parse_asteroids(LARGE_EXAMPLE);
+        let order = vaporisation_order(Point::new(11, 13), &asteroids);
+        let checks = [
+            (1, (11, 12)),
+            (2, (12, 1)),
+            (3, (12, 2)),
+            (10, (12, 8)),
+            (20, (16, 0)),
+            (50, (16, 9)),
+            (100, (10, 16)),
+            (199, (9, 6)),
+            (200, (8, 2)),
+            (201, (10, 9)),
+            (299, (11, 1)),
+        ];
+        for &(n, (x, y)) in checks.iter() {
+            assert_eq!(order[n - 1], Point::new(x, y), "asteroid {}", n);
+        }
+        assert_eq!(order.len(), 299);
+    }
+
+    #[test]
+    fn clockwise_starts_straight_up() {
+        let directions = [
+            Point::new(0, -1),
+            Point::new(1, -1),
+            Point::new(1, 0),
+            Point::new(1, 1),
+            Point::new(0, 1),
+            Point::new(-1, 1),
+            Point::new(-1, 0),
+            Point::new(-1, -1),
+        ];
+        for pair in directions.windows(2) {
+            assert_eq!(clockwise(pair[0], pair[1]), Ordering::Less);
+            assert_eq!(clockwise(pair[1], pair[0]), Ordering::Greater);
+        }
+    }
+}
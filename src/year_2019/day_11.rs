@@ -0,0 +1,109 @@
+use crate::year_2019::intcode::canvas::{self, Canvas, Palette};
+use crate::year_2019::intcode::{parse_program, Intcode, Status};
+use crate::year_2019::ocr;
+use crate::year_2019::point::{Direction, Point};
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::fs;
+
+const BLACK: i64 = 0;
+const WHITE: i64 = 1;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_11_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let hull = paint_hull(&parse_program(&input), BLACK);
+    println!("Panels painted at least once: {}", hull.painted.len());
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_11_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let hull = paint_hull(&parse_program(&input), WHITE);
+    print!("{}", hull.canvas.render());
+    let identifier = ocr::read_letters(&hull.canvas.mask(WHITE)).expect("Cannot read identifier");
+    println!("Registration identifier: {}", identifier);
+}
+
+struct Hull {
+    canvas: Canvas,
+    painted: HashSet<Point>,
+}
+
+/// Runs the robot on a black hull, apart from the starting panel which has
+/// the colour `start`. For each panel the robot is on, the camera input is
+/// the panel colour and the program answers with a paint colour and a turn,
+/// 0 for left and 1 for right, before the robot moves one panel forward.
+fn paint_hull(program: &[i64], start: i64) -> Hull {
+    let mut intcode = Intcode::new(program.to_vec()).expect("Program cannot be empty");
+    intcode.reset();
+    let mut canvas = Canvas::new(Palette::default());
+    canvas.paint(0, 0, start);
+    let robot = Cell::new((Point::new(0, 0), Direction::Up));
+    let mut painted = HashSet::new();
+    let status = canvas::play(
+        &mut intcode,
+        &mut canvas,
+        2,
+        |canvas, values| {
+            let (position, direction) = robot.get();
+            canvas.paint(position.x, position.y, values[0]);
+            painted.insert(position);
+            let direction = match values[1] {
+                0 => direction.turn_left(),
+                1 => direction.turn_right(),
+                turn => panic!("Unknown turn {}", turn),
+            };
+            robot.set((position + direction.offset(), direction));
+        },
+        |canvas| {
+            let (position, _) = robot.get();
+            Some(canvas.get(position.x, position.y).unwrap_or(BLACK))
+        },
+    );
+    assert_eq!(status, Ok(Status::Halted), "Robot program failed");
+    Hull { canvas, painted }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A program that reads the camera before each instruction and then
+    /// answers with the given paint colours and turns.
+    fn scripted_robot(instructions: &[(i64, i64)]) -> Vec<i64> {
+        let mut program = vec![];
+        for &(colour, turn) in instructions {
+            program.extend(vec![3, 1000, 104, colour, 104, turn]);
+        }
+        program.push(99);
+        program
+    }
+
+    #[test]
+    fn example_11_1() {
+        let program = scripted_robot(&[(1, 0), (0, 0), (1, 0), (1, 0), (0, 1), (1, 0), (1, 0)]);
+        let hull = paint_hull(&program, BLACK);
+        assert_eq!(hull.painted.len(), 6);
+        assert_eq!(hull.canvas.render(), "  █\n  █\n██ \n");
+    }
+
+    #[test]
+    fn camera_sees_painted_panels() {
+        // Repaints every panel with the colour the camera reports and turns
+        // right, so the fifth reading is the starting panel again.
+        let mut program = vec![];
+        for _ in 0..5 {
+            program.extend(vec![3, 1000, 4, 1000, 104, 1]);
+        }
+        program.push(99);
+        let hull = paint_hull(&program, WHITE);
+        assert_eq!(hull.painted.len(), 4);
+        assert_eq!(hull.canvas.count(WHITE), 1);
+        assert_eq!(hull.canvas.get(0, 0), Some(WHITE));
+        let hull = paint_hull(&program, BLACK);
+        assert_eq!(hull.canvas.count(WHITE), 0);
+    }
+}
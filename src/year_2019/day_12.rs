@@ -0,0 +1,192 @@
+use crate::year_2019::point::lcm;
+use regex::Regex;
+use std::fs;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_12_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let mut simulation = NBodySimulation::new(&parse_moons(&input));
+    simulation.run(1000);
+    println!(
+        "Total energy after 1000 steps: {}",
+        simulation.total_energy()
+    );
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_12_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let simulation = NBodySimulation::new(&parse_moons(&input));
+    println!("Steps until the system repeats: {}", simulation.period());
+}
+
+fn parse_moons(input: &str) -> Vec<[i64; 3]> {
+    let re = Regex::new(r"<x=(-?\d+), y=(-?\d+), z=(-?\d+)>").expect("Could not create regex.");
+    re.captures_iter(input)
+        .map(|cap| {
+            let coordinate = |i: usize| cap[i].parse::<i64>().expect("Cannot parse coordinate");
+            [coordinate(1), coordinate(2), coordinate(3)]
+        })
+        .collect()
+}
+
+/// Moons pulling on each other, stored axis by axis because the axes never
+/// influence each other.
+pub struct NBodySimulation {
+    positions: [Vec<i64>; 3],
+    velocities: [Vec<i64>; 3],
+}
+
+impl NBodySimulation {
+    pub fn new(moons: &[[i64; 3]]) -> Self {
+        let axis = |a: usize| moons.iter().map(|moon| moon[a]).collect::<Vec<i64>>();
+        NBodySimulation {
+            positions: [axis(0), axis(1), axis(2)],
+            velocities: [
+                vec![0; moons.len()],
+                vec![0; moons.len()],
+                vec![0; moons.len()],
+            ],
+        }
+    }
+
+    fn step(&mut self) {
+        for (positions, velocities) in self.positions.iter_mut().zip(self.velocities.iter_mut()) {
+            step_axis(positions, velocities);
+        }
+    }
+
+    pub fn run(&mut self, num_steps: i64) {
+        for _ in 0..num_steps {
+            self.step();
+        }
+    }
+
+    /// Sum over all moons of their potential energy times their kinetic
+    /// energy.
+    pub fn total_energy(&self) -> i64 {
+        let energy = |values: &[Vec<i64>; 3], moon: usize| -> i64 {
+            values.iter().map(|axis| axis[moon].abs()).sum()
+        };
+        (0..self.positions[0].len())
+            .map(|moon| energy(&self.positions, moon) * energy(&self.velocities, moon))
+            .sum()
+    }
+
+    /// Number of steps until positions and velocities repeat. Each axis
+    /// repeats on its own period and the whole system repeats once all of
+    /// them line up.
+    pub fn period(&self) -> i64 {
+        (0..3)
+            .map(|a| axis_period(&self.positions[a], &self.velocities[a]))
+            .fold(1, lcm)
+    }
+}
+
+/// Applies gravity between every pair of moons and then velocity, along a
+/// single axis.
+fn step_axis(positions: &mut [i64], velocities: &mut [i64]) {
+    for (i, velocity) in velocities.iter_mut().enumerate() {
+        *velocity += positions
+            .iter()
+            .map(|other| (other - positions[i]).signum())
+            .sum::<i64>();
+    }
+    for (position, velocity) in positions.iter_mut().zip(velocities.iter()) {
+        *position += velocity;
+    }
+}
+
+/// A step can be undone, so the first repeated state of an axis is always
+/// its starting state.
+fn axis_period(positions: &[i64], velocities: &[i64]) -> i64 {
+    let mut current = (positions.to_vec(), velocities.to_vec());
+    let mut steps = 0;
+    loop {
+        step_axis(&mut current.0, &mut current.1);
+        steps += 1;
+        if current.0 == positions && current.1 == velocities {
+            return steps;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>";
+
+    const EXAMPLE_2: &str = "<x=-8, y=-10, z=0>
+<x=5, y=5, z=10>
+<x=2, y=-7, z=3>
+<x=9, y=-8, z=-3>";
+
+    #[test]
+    fn check_step() {
+        let mut simulation = NBodySimulation::new(&parse_moons(EXAMPLE_1));
+        simulation.step();
+        assert_eq!(
+            simulation.positions,
+            [vec![2, 3, 1, 2], vec![-1, -7, -7, 2], vec![1, -4, 5, 0]]
+        );
+        assert_eq!(
+            simulation.velocities,
+            [vec![3, 1, -3, -1], vec![-1, 3, 1, -3], vec![-1, 3, -3, 1]]
+        );
+    }
+
+    #[test]
+    fn example_12_1() {
+        let mut simulation = NBodySimulation::new(&parse_moons(EXAMPLE_1));
+        simulation.run(10);
+        assert_eq!(simulation.total_energy(), 179);
+    }
+
+    #[test]
+    fn example_12_2() {
+        let mut simulation = NBodySimulation::new(&parse_moons(EXAMPLE_2));
+        simulation.run(100);
+        assert_eq!(simulation.total_energy(), 1940);
+    }
+
+    #[test]
+    fn example_12_3() {
+        let mut simulation = NBodySimulation::new(&parse_moons(EXAMPLE_1));
+        assert_eq!(simulation.period(), 2772);
+        let start = (simulation.positions.clone(), simulation.velocities.clone());
+        simulation.run(2772);
+        assert_eq!((simulation.positions, simulation.velocities), start);
+    }
+
+    #[test]
+    fn example_12_4() {
+        let simulation = NBodySimulation::new(&parse_moons(EXAMPLE_2));
+        assert_eq!(simulation.period(), 4686774924);
+    }
+
+    #[test]
+    fn any_number_of_bodies() {
+        let mut simulation = NBodySimulation::new(&[[0, 0, 0], [2, 0, 0]]);
+        simulation.run(1);
+        assert_eq!(simulation.positions[0], vec![1, 1]);
+        let moons = [[0, 3, -1], [2, 0, 4], [5, -2, 1]];
+        let mut simulation = NBodySimulation::new(&moons);
+        let start = (simulation.positions.clone(), simulation.velocities.clone());
+        let mut steps = 0;
+        loop {
+            simulation.step();
+            steps += 1;
+            if (&simulation.positions, &simulation.velocities) == (&start.0, &start.1) {
+                break;
+            }
+        }
+        assert_eq!(NBodySimulation::new(&moons).period(), steps);
+        assert_eq!(NBodySimulation::new(&[[1, 2, 3]]).period(), 1);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::year_2019::intcode::canvas::{self, Canvas, Palette, Tile};
+use crate::year_2019::intcode::{parse_program, Intcode, Status};
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+const EMPTY: i64 = 0;
+const WALL: i64 = 1;
+const BLOCK: i64 = 2;
+const PADDLE: i64 = 3;
+const BALL: i64 = 4;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_13_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let screen = draw_screen(&parse_program(&input));
+    println!("Block tiles: {}", screen.count(BLOCK));
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_13_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let score = play_game(&parse_program(&input), false);
+    println!("Final score: {}", score);
+}
+
+/// Plays the game like `part_2`, but shows every frame in the terminal.
+#[allow(dead_code)]
+pub fn watch() {
+    let location = "input/year_2019/day_13_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let score = play_game(&parse_program(&input), true);
+    println!("Final score: {}", score);
+}
+
+fn palette() -> Palette {
+    Palette::new(Tile {
+        glyph: ' ',
+        color: [0, 0, 0],
+    })
+    .with(EMPTY, ' ', [0, 0, 0])
+    .with(WALL, '█', [128, 128, 128])
+    .with(BLOCK, '▒', [200, 80, 40])
+    .with(PADDLE, '▔', [255, 255, 255])
+    .with(BALL, 'o', [255, 255, 0])
+}
+
+/// Runs the program without a coin and returns the screen it draws.
+fn draw_screen(program: &[i64]) -> Canvas {
+    let mut intcode = Intcode::new(program.to_vec()).expect("Program cannot be empty");
+    intcode.reset();
+    let mut screen = Canvas::new(palette());
+    let status = canvas::play(&mut intcode, &mut screen, 3, canvas::draw_tiles, |_| None);
+    assert!(status.is_ok(), "Arcade program failed");
+    screen
+}
+
+/// Moves the paddle towards the ball.
+fn autopilot(screen: &mut Canvas) -> Option<i64> {
+    let (ball, _) = screen.find(BALL)?;
+    let (paddle, _) = screen.find(PADDLE)?;
+    Some((ball - paddle).signum())
+}
+
+/// Inserts two quarters, plays until the game ends and returns the last
+/// score shown. The score is drawn at the position (-1, 0).
+fn play_game(program: &[i64], watch: bool) -> i64 {
+    let mut program = program.to_vec();
+    program[0] = 2;
+    let mut intcode = Intcode::new(program).expect("Program cannot be empty");
+    intcode.reset();
+    let mut screen = Canvas::new(palette());
+    let mut score = 0;
+    let status = canvas::play(
+        &mut intcode,
+        &mut screen,
+        3,
+        |screen, values| match values {
+            [-1, 0, value] => score = *value,
+            _ => canvas::draw_tiles(screen, values),
+        },
+        |screen| {
+            if watch {
+                screen.print_frame();
+                thread::sleep(Duration::from_millis(10));
+            }
+            autopilot(screen)
+        },
+    );
+    assert_eq!(status, Ok(Status::Halted), "Arcade program failed");
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_can_be_overwritten() {
+        let program = vec![
+            104, 0, 104, 0, 104, 2, 104, 1, 104, 0, 104, 2, 104, 0, 104, 0, 104, 0, 104, 2, 104, 0,
+            104, 1, 99,
+        ];
+        let screen = draw_screen(&program);
+        assert_eq!(screen.count(BLOCK), 1);
+        assert_eq!(screen.count(WALL), 1);
+        assert_eq!(screen.render(), " ▒█\n");
+    }
+
+    #[test]
+    fn autopilot_follows_ball() {
+        // Starts with an instruction that stays harmless when the quarters
+        // turn it into a multiplication. Then draws the paddle at (1, 2) and
+        // the ball at (3, 1), reads the joystick, moves the ball to (0, 1),
+        // reads the joystick again and shows ten times the first reading
+        // plus the second as the score.
+        let program = vec![
+            1, 0, 0, 1000, 104, 1, 104, 2, 104, 3, 104, 3, 104, 1, 104, 4, 3, 100, 104, 3, 104, 1,
+            104, 0, 104, 0, 104, 1, 104, 4, 3, 101, 1002, 100, 10, 102, 1, 102, 101, 102, 104, -1,
+            104, 0, 4, 102, 99,
+        ];
+        assert_eq!(play_game(&program, false), 9);
+    }
+
+    #[test]
+    fn autopilot_waits_without_paddle() {
+        let mut screen = Canvas::new(palette());
+        screen.paint(3, 1, BALL);
+        assert_eq!(autopilot(&mut screen), None);
+        screen.paint(3, 5, PADDLE);
+        assert_eq!(autopilot(&mut screen), Some(0));
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::fs;
+
+const ORE: &str = "ORE";
+const FUEL: &str = "FUEL";
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_14_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let factory = parse_reactions(&input).expect("Invalid reactions");
+    println!("ORE for one FUEL: {}", factory.ore_for_fuel(1));
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_14_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let factory = parse_reactions(&input).expect("Invalid reactions");
+    println!(
+        "FUEL from one trillion ORE: {}",
+        factory.max_fuel(1_000_000_000_000)
+    );
+}
+
+#[derive(Debug, PartialEq)]
+enum ReactionError {
+    Malformed(String),
+    DuplicateProducer(String),
+    MissingProducer(String),
+    ProducesOre(String),
+    Cycle(String),
+}
+
+struct Reaction {
+    quantity: u64,
+    inputs: Vec<(String, u64)>,
+}
+
+/// The reactions by the chemical they produce, together with every chemical
+/// ordered so that it comes before all chemicals it is made from.
+struct Nanofactory {
+    reactions: HashMap<String, Reaction>,
+    order: Vec<String>,
+}
+
+fn parse_chemical(input: &str) -> Option<(String, u64)> {
+    match input.split_whitespace().collect::<Vec<&str>>()[..] {
+        [quantity, name] => Some((name.to_string(), quantity.parse::<u64>().ok()?)),
+        _ => None,
+    }
+}
+
+fn parse_reactions(input: &str) -> Result<Nanofactory, ReactionError> {
+    let mut reactions: HashMap<String, Reaction> = HashMap::new();
+    for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let malformed = || ReactionError::Malformed(line.to_string());
+        let (inputs, output) = match line.split("=>").collect::<Vec<&str>>()[..] {
+            [inputs, output] => (inputs, output),
+            _ => return Err(malformed()),
+        };
+        let (name, quantity) = parse_chemical(output).ok_or_else(malformed)?;
+        let inputs = inputs
+            .split(',')
+            .map(parse_chemical)
+            .collect::<Option<Vec<(String, u64)>>>()
+            .ok_or_else(malformed)?;
+        if quantity == 0 || inputs.iter().any(|&(_, quantity)| quantity == 0) {
+            return Err(malformed());
+        }
+        if name == ORE {
+            return Err(ReactionError::ProducesOre(line.to_string()));
+        }
+        if reactions.contains_key(&name) {
+            return Err(ReactionError::DuplicateProducer(name));
+        }
+        reactions.insert(name, Reaction { quantity, inputs });
+    }
+    let order = topological_order(&reactions)?;
+    Ok(Nanofactory { reactions, order })
+}
+
+/// Orders all chemicals so that every chemical comes before its inputs,
+/// using a depth-first search that reports a chemical reached again while
+/// its own inputs are still being visited.
+fn topological_order(reactions: &HashMap<String, Reaction>) -> Result<Vec<String>, ReactionError> {
+    fn visit<'a>(
+        chemical: &'a str,
+        reactions: &'a HashMap<String, Reaction>,
+        finished: &mut HashMap<&'a str, bool>,
+        order: &mut Vec<String>,
+    ) -> Result<(), ReactionError> {
+        match finished.get(chemical) {
+            Some(true) => return Ok(()),
+            Some(false) => return Err(ReactionError::Cycle(chemical.to_string())),
+            None => (),
+        }
+        finished.insert(chemical, false);
+        if chemical != ORE {
+            let reaction = reactions
+                .get(chemical)
+                .ok_or_else(|| ReactionError::MissingProducer(chemical.to_string()))?;
+            for (input, _) in &reaction.inputs {
+                visit(input, reactions, finished, order)?;
+            }
+        }
+        finished.insert(chemical, true);
+        order.push(chemical.to_string());
+        Ok(())
+    }
+
+    let mut finished: HashMap<&str, bool> = HashMap::new();
+    let mut order = vec![];
+    visit(FUEL, reactions, &mut finished, &mut order)?;
+    let mut chemicals: Vec<&String> = reactions.keys().collect();
+    chemicals.sort();
+    for chemical in chemicals {
+        visit(chemical, reactions, &mut finished, &mut order)?;
+    }
+    order.reverse();
+    Ok(order)
+}
+
+impl Nanofactory {
+    /// Runs just enough reactions to make `fuel` FUEL and returns the ORE
+    /// used and the chemicals left over. Each chemical is handled once, after
+    /// everything that consumes it, so its full demand is known by then.
+    fn produce(&self, fuel: u64) -> (u64, HashMap<String, u64>) {
+        let mut needed: HashMap<&str, u64> = HashMap::new();
+        let mut leftovers = HashMap::new();
+        needed.insert(FUEL, fuel);
+        for chemical in &self.order {
+            let amount = needed.get(chemical.as_str()).copied().unwrap_or(0);
+            let reaction = match self.reactions.get(chemical) {
+                Some(reaction) if 0 < amount => reaction,
+                _ => continue,
+            };
+            let batches = amount.div_ceil(reaction.quantity);
+            let surplus = batches * reaction.quantity - amount;
+            if 0 < surplus {
+                leftovers.insert(chemical.clone(), surplus);
+            }
+            for (input, quantity) in &reaction.inputs {
+                *needed.entry(input).or_insert(0) += batches * quantity;
+            }
+        }
+        (needed.get(ORE).copied().unwrap_or(0), leftovers)
+    }
+
+    fn ore_for_fuel(&self, fuel: u64) -> u64 {
+        self.produce(fuel).0
+    }
+
+    /// The most FUEL that can be made from `ore` ORE, found by doubling an
+    /// upper bound and then searching between the bounds.
+    fn max_fuel(&self, ore: u64) -> u64 {
+        let mut low = 0;
+        let mut high = 1;
+        while self.ore_for_fuel(high) <= ore {
+            low = high;
+            high *= 2;
+        }
+        while low + 1 < high {
+            let middle = low + (high - low) / 2;
+            if self.ore_for_fuel(middle) <= ore {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "10 ORE => 10 A
+1 ORE => 1 B
+7 A, 1 B => 1 C
+7 A, 1 C => 1 D
+7 A, 1 D => 1 E
+7 A, 1 E => 1 FUEL";
+
+    const EXAMPLE_2: &str = "9 ORE => 2 A
+8 ORE => 3 B
+7 ORE => 5 C
+3 A, 4 B => 1 AB
+5 B, 7 C => 1 BC
+4 C, 1 A => 1 CA
+2 AB, 3 BC, 4 CA => 1 FUEL";
+
+    const EXAMPLE_3: &str = "157 ORE => 5 NZVS
+165 ORE => 6 DCFZ
+44 XJWVT, 5 KHKGT, 1 QDVJ, 29 NZVS, 9 GPVTF, 48 HKGWZ => 1 FUEL
+12 HKGWZ, 1 GPVTF, 8 PSHF => 9 QDVJ
+179 ORE => 7 PSHF
+177 ORE => 5 HKGWZ
+7 DCFZ, 7 PSHF => 2 XJWVT
+165 ORE => 2 GPVTF
+3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT";
+
+    const EXAMPLE_5: &str = "171 ORE => 8 CNZTR
+7 ZLQW, 3 BMBT, 9 XCVML, 26 XMNCP, 1 WPTQ, 2 MZWV, 1 RJRHP => 4 PLWSL
+114 ORE => 4 BHXH
+14 VRPVC => 6 BMBT
+6 BHXH, 18 KTJDG, 12 WPTQ, 7 PLWSL, 31 FHTLT, 37 ZDVW => 1 FUEL
+6 WPTQ, 2 BMBT, 8 ZLQW, 18 KTJDG, 1 XMNCP, 6 MZWV, 1 RJRHP => 6 FHTLT
+15 XDBXC, 2 LTCX, 1 VRPVC => 6 ZLQW
+13 WPTQ, 10 LTCX, 3 RJRHP, 14 XMNCP, 2 MZWV, 1 ZLQW => 1 ZDVW
+5 BMBT => 4 WPTQ
+189 ORE => 9 KTJDG
+1 MZWV, 17 XDBXC, 3 XCVML => 2 XMNCP
+12 VRPVC, 27 CNZTR => 2 XDBXC
+15 KTJDG, 12 BHXH => 5 XCVML
+3 BHXH, 2 VRPVC => 7 MZWV
+121 ORE => 7 VRPVC
+7 XCVML => 6 RJRHP
+5 BHXH, 4 VRPVC => 5 LTCX";
+
+    #[test]
+    fn example_14_1() {
+        let factory = parse_reactions(EXAMPLE_1).unwrap();
+        let (ore, leftovers) = factory.produce(1);
+        assert_eq!(ore, 31);
+        assert_eq!(leftovers.get("A"), Some(&2));
+        assert_eq!(leftovers.len(), 1);
+    }
+
+    #[test]
+    fn example_14_2() {
+        let factory = parse_reactions(EXAMPLE_2).unwrap();
+        assert_eq!(factory.ore_for_fuel(1), 165);
+    }
+
+    #[test]
+    fn example_14_3() {
+        let factory = parse_reactions(EXAMPLE_3).unwrap();
+        assert_eq!(factory.ore_for_fuel(1), 13312);
+        assert_eq!(factory.max_fuel(1_000_000_000_000), 82892753);
+    }
+
+    #[test]
+    fn example_14_5() {
+        let factory = parse_reactions(EXAMPLE_5).unwrap();
+        assert_eq!(factory.ore_for_fuel(1), 2210736);
+        assert_eq!(factory.max_fuel(1_000_000_000_000), 460664);
+    }
+
+    #[test]
+    fn order_puts_consumers_first() {
+        let factory = parse_reactions(EXAMPLE_1).unwrap();
+        let position = |c: &str| factory.order.iter().position(|o| o == c).unwrap();
+        assert_eq!(position(FUEL), 0);
+        assert!(position("E") < position("D"));
+        assert!(position("C") < position("A"));
+        assert_eq!(position(ORE), factory.order.len() - 1);
+    }
+
+    #[test]
+    fn max_fuel_with_little_ore() {
+        let factory = parse_reactions(EXAMPLE_1).unwrap();
+        assert_eq!(factory.max_fuel(30), 0);
+        assert_eq!(factory.max_fuel(31), 1);
+    }
+
+    #[test]
+    fn invalid_reactions_are_rejected() {
+        let cases = vec![
+            (
+                "1 ORE => 1 A\n2 ORE => 1 A\n1 A => 1 FUEL",
+                ReactionError::DuplicateProducer(String::from("A")),
+            ),
+            (
+                "1 B => 1 FUEL",
+                ReactionError::MissingProducer(String::from("B")),
+            ),
+            (
+                "1 ORE => 1 A",
+                ReactionError::MissingProducer(String::from(FUEL)),
+            ),
+            (
+                "1 A => 1 B\n1 B => 1 A\n1 A => 1 FUEL",
+                ReactionError::Cycle(String::from("A")),
+            ),
+            (
+                "1 A => 2 ORE\n1 ORE => 1 FUEL",
+                ReactionError::ProducesOre(String::from("1 A => 2 ORE")),
+            ),
+            (
+                "1 ORE -> 1 FUEL",
+                ReactionError::Malformed(String::from("1 ORE -> 1 FUEL")),
+            ),
+            (
+                "1 ORE, => 1 FUEL",
+                ReactionError::Malformed(String::from("1 ORE, => 1 FUEL")),
+            ),
+            (
+                "0 ORE => 1 FUEL",
+                ReactionError::Malformed(String::from("0 ORE => 1 FUEL")),
+            ),
+        ];
+        for (input, error) in cases {
+            assert_eq!(parse_reactions(input).err(), Some(error), "{}", input);
+        }
+    }
+
+    #[test]
+    fn unreachable_cycles_are_rejected() {
+        let result = parse_reactions("1 ORE => 1 FUEL\n1 X => 1 Y\n1 Y => 1 X");
+        assert!(matches!(result.err(), Some(ReactionError::Cycle(_))));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::year_2019::intcode::canvas::{Canvas, Palette, Tile};
+use crate::year_2019::intcode::{parse_program, Intcode, Status};
+use crate::year_2019::point::{Direction, Point};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+
+const WALL: i64 = 0;
+const OPEN: i64 = 1;
+const OXYGEN: i64 = 2;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_15_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let map = explore(droid(&parse_program(&input)));
+    print!("{}", map.render());
+    let oxygen = map.find(OXYGEN).expect("Oxygen system not found");
+    let distances = distances(&map, Point::new(0, 0));
+    println!(
+        "Moves to the oxygen system: {}",
+        distances[&Point::new(oxygen.0, oxygen.1)]
+    );
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_15_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let map = explore(droid(&parse_program(&input)));
+    let (x, y) = map.find(OXYGEN).expect("Oxygen system not found");
+    println!(
+        "Minutes to fill with oxygen: {}",
+        fill_time(&map, Point::new(x, y))
+    );
+}
+
+fn palette() -> Palette {
+    Palette::new(Tile {
+        glyph: ' ',
+        color: [0, 0, 0],
+    })
+    .with(WALL, '█', [128, 128, 128])
+    .with(OPEN, '.', [255, 255, 255])
+    .with(OXYGEN, 'O', [0, 128, 255])
+}
+
+/// Movement commands are 1 to 4 for north, south, west and east.
+fn command(direction: Direction) -> i64 {
+    match direction {
+        Direction::Up => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        Direction::Right => 4,
+    }
+}
+
+/// The repair droid as a function that tries to move in a direction and
+/// returns the status reply: hit a wall, moved, or moved onto the oxygen
+/// system.
+fn droid(program: &[i64]) -> impl FnMut(Direction) -> i64 {
+    let mut intcode = Intcode::new(program.to_vec()).expect("Program cannot be empty");
+    intcode.reset();
+    move |direction| {
+        let status = intcode
+            .push_input(command(direction))
+            .run()
+            .expect("Droid program failed");
+        assert!(
+            matches!(status, Status::AwaitingInput { .. }),
+            "Droid stopped"
+        );
+        intcode.take_output().expect("Droid did not reply")
+    }
+}
+
+/// Maps the whole maze with a depth-first search that walks the droid back
+/// after every dead end. The droid starts at (0, 0) on an open tile.
+fn explore<F: FnMut(Direction) -> i64>(mut droid: F) -> Canvas {
+    fn visit<F: FnMut(Direction) -> i64>(droid: &mut F, map: &mut Canvas, position: Point) {
+        for &direction in Direction::ALL.iter() {
+            let next = position + direction.offset();
+            if map.get(next.x, next.y).is_some() {
+                continue;
+            }
+            let status = droid(direction);
+            map.paint(next.x, next.y, status);
+            if status != WALL {
+                visit(droid, map, next);
+                droid(direction.reverse());
+            }
+        }
+    }
+
+    let mut map = Canvas::new(palette());
+    map.paint(0, 0, OPEN);
+    visit(&mut droid, &mut map, Point::new(0, 0));
+    map
+}
+
+/// Fewest moves from `start` to every reachable tile.
+fn distances(map: &Canvas, start: Point) -> HashMap<Point, usize> {
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    distances.insert(start, 0);
+    queue.push_back(start);
+    while let Some(position) = queue.pop_front() {
+        let distance = distances[&position];
+        for &direction in Direction::ALL.iter() {
+            let next = position + direction.offset();
+            let open = matches!(map.get(next.x, next.y), Some(OPEN) | Some(OXYGEN));
+            if open && !distances.contains_key(&next) {
+                distances.insert(next, distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// Minutes until oxygen spreading one tile per minute from `source` fills
+/// every reachable tile.
+fn fill_time(map: &Canvas, source: Point) -> usize {
+    distances(map, source).values().copied().max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A droid in a maze drawn with `#` walls, `D` as the start and `O` as
+    /// the oxygen system. It reports where it is after every move.
+    fn simulated_droid(maze: &str) -> impl FnMut(Direction) -> (i64, Point) {
+        let mut tiles = HashMap::new();
+        let mut position = Point::new(0, 0);
+        for (y, line) in maze.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let point = Point::new(x as i64, y as i64);
+                let tile = match c {
+                    '.' => OPEN,
+                    'O' => OXYGEN,
+                    'D' => {
+                        position = point;
+                        OPEN
+                    }
+                    _ => WALL,
+                };
+                tiles.insert(point, tile);
+            }
+        }
+        move |direction| {
+            let next = position + direction.offset();
+            let tile = tiles[&next];
+            if tile != WALL {
+                position = next;
+            }
+            (tile, position)
+        }
+    }
+
+    const MAZE: &str = "#######
+#...#O#
+#.#.#.#
+#.#D..#
+#######";
+
+    #[test]
+    fn explores_whole_maze() {
+        let mut droid = simulated_droid(MAZE);
+        let map = explore(|direction| droid(direction).0);
+        assert_eq!(map.count(OPEN), 10);
+        assert_eq!(map.find(OXYGEN), Some((2, -2)));
+        assert_eq!(map.get(-2, -2), Some(OPEN));
+        assert_eq!(map.get(-1, -1), Some(WALL));
+        assert_eq!(map.get(-3, -3), None);
+    }
+
+    #[test]
+    fn shortest_path_to_oxygen() {
+        let mut droid = simulated_droid(MAZE);
+        let map = explore(|direction| droid(direction).0);
+        assert_eq!(distances(&map, Point::new(0, 0))[&Point::new(2, -2)], 4);
+    }
+
+    #[test]
+    fn droid_backtracks_to_start() {
+        let mut droid = simulated_droid(MAZE);
+        let mut position = None;
+        explore(|direction| {
+            let (tile, at) = droid(direction);
+            position = Some(at);
+            tile
+        });
+        assert_eq!(position, Some(Point::new(3, 3)));
+    }
+
+    #[test]
+    fn example_15_1() {
+        let maze = " ##
+#D.##
+#.#..#
+#.O.#
+ ###";
+        let mut droid = simulated_droid(maze);
+        let map = explore(|direction| droid(direction).0);
+        assert_eq!(map.render(), " ██   \n█..██ \n█.█..█\n█.O.█ \n ███  \n");
+        let (x, y) = map.find(OXYGEN).unwrap();
+        assert_eq!(distances(&map, Point::new(0, 0))[&Point::new(x, y)], 3);
+        assert_eq!(fill_time(&map, Point::new(x, y)), 4);
+    }
+}
@@ -0,0 +1,143 @@
+use std::fs;
+
+const BASE_PATTERN: [i64; 4] = [0, 1, 0, -1];
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_16_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let signal = run_phases(&parse_signal(&input), 100, naive_phase);
+    println!("First eight digits: {}", digits(&signal[..8]));
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_16_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    println!(
+        "Embedded message: {}",
+        decode_message(&parse_signal(&input))
+    );
+}
+
+fn parse_signal(input: &str) -> Vec<i64> {
+    input
+        .trim()
+        .chars()
+        .map(|c| c.to_digit(10).expect("Cannot parse digit") as i64)
+        .collect()
+}
+
+fn digits(signal: &[i64]) -> String {
+    signal.iter().map(|d| d.to_string()).collect()
+}
+
+/// Computes one phase of a signal.
+type Phase = fn(&[i64]) -> Vec<i64>;
+
+fn run_phases(signal: &[i64], phases: usize, phase: Phase) -> Vec<i64> {
+    (0..phases).fold(signal.to_vec(), |signal, _| phase(&signal))
+}
+
+/// Output digit `i` is the last digit of the signal multiplied element-wise
+/// with the base pattern, where every pattern value is repeated `i + 1`
+/// times and the very first value is skipped.
+fn naive_phase(signal: &[i64]) -> Vec<i64> {
+    (0..signal.len())
+        .map(|i| {
+            let sum: i64 = signal
+                .iter()
+                .enumerate()
+                .map(|(j, digit)| digit * BASE_PATTERN[(j + 1) / (i + 1) % 4])
+                .sum();
+            sum.abs() % 10
+        })
+        .collect()
+}
+
+/// One phase of the tail of a signal that starts in its second half. There
+/// the pattern is zero up to the position and one after it, so each output
+/// digit is the sum of the digits from its position to the end.
+fn suffix_phase(tail: &[i64]) -> Vec<i64> {
+    let mut output = vec![0; tail.len()];
+    let mut sum = 0;
+    for (i, digit) in tail.iter().enumerate().rev() {
+        sum = (sum + digit) % 10;
+        output[i] = sum;
+    }
+    output
+}
+
+/// Repeats the signal 10000 times and reads the eight digits at the offset
+/// given by the first seven digits after 100 phases. The offset has to be in
+/// the second half, so only the tail from the offset is transformed.
+fn decode_message(signal: &[i64]) -> String {
+    let offset = digits(&signal[..7])
+        .parse::<usize>()
+        .expect("Cannot parse offset");
+    let length = signal.len() * 10000;
+    assert!(
+        length / 2 <= offset,
+        "The message must be in the second half"
+    );
+    let tail: Vec<i64> = (offset..length).map(|i| signal[i % signal.len()]).collect();
+    let output = run_phases(&tail, 100, suffix_phase);
+    digits(&output[..8])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_16_1() {
+        let signal = parse_signal("12345678");
+        let expected = ["48226158", "34040438", "03415518", "01029498"];
+        for (phases, output) in expected.iter().enumerate() {
+            let signal = run_phases(&signal, phases + 1, naive_phase);
+            assert_eq!(digits(&signal), *output);
+        }
+    }
+
+    #[test]
+    fn example_16_2() {
+        let cases = [
+            ("80871224585914546619083218645595", "24176176"),
+            ("19617804207202209144916044189917", "73745418"),
+            ("69317163492948606335995924319873", "52432133"),
+        ];
+        for (input, output) in cases.iter() {
+            let signal = run_phases(&parse_signal(input), 100, naive_phase);
+            assert_eq!(digits(&signal[..8]), *output);
+        }
+    }
+
+    #[test]
+    fn example_16_3() {
+        let cases = [
+            ("03036732577212944063491565474664", "84462026"),
+            ("02935109699940807407585447034323", "78725270"),
+            ("03081770884921959731165446850517", "53553731"),
+        ];
+        for (input, output) in cases.iter() {
+            assert_eq!(decode_message(&parse_signal(input)), *output);
+        }
+    }
+
+    #[test]
+    fn suffix_phase_agrees_with_naive_phase() {
+        let signals = [
+            "12345678",
+            "80871224585914546619083218645595",
+            "9999999999999999999999999999999",
+            "0000000001",
+        ];
+        for input in signals.iter() {
+            let signal = parse_signal(input);
+            let half = signal.len() / 2;
+            let naive = run_phases(&signal, 10, naive_phase);
+            let fast = run_phases(&signal[half..], 10, suffix_phase);
+            assert_eq!(naive[half..], fast[..], "{}", input);
+        }
+    }
+}
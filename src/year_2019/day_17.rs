@@ -0,0 +1,310 @@
+use crate::year_2019::intcode::{ascii, parse_program, Intcode};
+use crate::year_2019::point::{Direction, Point};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+
+/// Most characters in the main routine and in each movement function,
+/// not counting the newline.
+const LIMIT: usize = 20;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_17_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let view = camera_view(&parse_program(&input));
+    print!("{}", view);
+    let (scaffold, _) = parse_view(&view);
+    println!("Sum of alignment parameters: {}", alignment_sum(&scaffold));
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_17_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let program = parse_program(&input);
+    let (scaffold, robot) = parse_view(&camera_view(&program));
+    let robot = robot.expect("Robot not found");
+    let path = find_path(&scaffold, robot);
+    let compression = compress(&path, 3, LIMIT).expect("Path cannot be compressed");
+    println!("Dust collected: {}", collect_dust(&program, &compression));
+}
+
+fn camera_view(program: &[i64]) -> String {
+    let mut intcode = Intcode::new(program.to_vec()).expect("Program cannot be empty");
+    intcode.reset().run().expect("Camera program failed");
+    ascii::take_text(&mut intcode).0
+}
+
+/// The scaffold positions and, if it is on the scaffold, the robot. The
+/// robot is drawn as `^`, `v`, `<` or `>` on top of a scaffold.
+fn parse_view(view: &str) -> (HashSet<Point>, Option<(Point, Direction)>) {
+    let mut scaffold = HashSet::new();
+    let mut robot = None;
+    for (y, line) in view.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let point = Point::new(x as i64, y as i64);
+            let direction = match c {
+                '#' => None,
+                '^' => Some(Direction::Up),
+                'v' => Some(Direction::Down),
+                '<' => Some(Direction::Left),
+                '>' => Some(Direction::Right),
+                _ => continue,
+            };
+            scaffold.insert(point);
+            if let Some(direction) = direction {
+                robot = Some((point, direction));
+            }
+        }
+    }
+    (scaffold, robot)
+}
+
+fn alignment_sum(scaffold: &HashSet<Point>) -> i64 {
+    scaffold
+        .iter()
+        .filter(|&&point| {
+            Direction::ALL
+                .iter()
+                .all(|d| scaffold.contains(&(point + d.offset())))
+        })
+        .map(|point| point.x * point.y)
+        .sum()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Instruction {
+    Left,
+    Right,
+    Forward(usize),
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Left => write!(f, "L"),
+            Instruction::Right => write!(f, "R"),
+            Instruction::Forward(steps) => write!(f, "{}", steps),
+        }
+    }
+}
+
+/// Walks straight ahead as far as the scaffold goes and turns only at
+/// corners, until the robot reaches a dead end.
+fn find_path(scaffold: &HashSet<Point>, robot: (Point, Direction)) -> Vec<Instruction> {
+    let (mut position, mut direction) = robot;
+    let mut path = vec![];
+    loop {
+        let mut steps = 0;
+        while scaffold.contains(&(position + direction.offset())) {
+            position = position + direction.offset();
+            steps += 1;
+        }
+        if 0 < steps {
+            path.push(Instruction::Forward(steps));
+        }
+        let left = direction.turn_left();
+        let right = direction.turn_right();
+        if scaffold.contains(&(position + left.offset())) {
+            direction = left;
+            path.push(Instruction::Left);
+        } else if scaffold.contains(&(position + right.offset())) {
+            direction = right;
+            path.push(Instruction::Right);
+        } else {
+            return path;
+        }
+    }
+}
+
+/// A path split into a main routine of calls to movement functions.
+#[derive(Debug, PartialEq)]
+struct Compression<T> {
+    main: Vec<usize>,
+    functions: Vec<Vec<T>>,
+}
+
+fn join<T: fmt::Display>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+impl<T: fmt::Display> Compression<T> {
+    /// The main routine with functions named A, B, C and so on.
+    fn main_routine(&self) -> String {
+        join(
+            &self
+                .main
+                .iter()
+                .map(|&f| (b'A' + f as u8) as char)
+                .collect::<Vec<char>>(),
+        )
+    }
+}
+
+/// Splits `path` into at most `functions` movement functions and a main
+/// routine calling them, where each of them is at most `limit` characters
+/// long when written out with commas. Every new function starts where the
+/// path is not covered yet, so trying all lengths for it finds a split
+/// whenever one exists.
+fn compress<T>(path: &[T], functions: usize, limit: usize) -> Option<Compression<T>>
+where
+    T: Clone + PartialEq + fmt::Display,
+{
+    fn search<T: Clone + PartialEq + fmt::Display>(
+        path: &[T],
+        compression: &mut Compression<T>,
+        max_functions: usize,
+        limit: usize,
+    ) -> bool {
+        if path.is_empty() {
+            return true;
+        }
+        if limit < 2 * compression.main.len() + 1 {
+            return false;
+        }
+        for f in 0..compression.functions.len() {
+            let length = compression.functions[f].len();
+            if path.starts_with(&compression.functions[f]) {
+                compression.main.push(f);
+                if search(&path[length..], compression, max_functions, limit) {
+                    return true;
+                }
+                compression.main.pop();
+            }
+        }
+        if compression.functions.len() < max_functions {
+            for length in 1..=path.len() {
+                if limit < join(&path[..length]).len() {
+                    break;
+                }
+                compression.main.push(compression.functions.len());
+                compression.functions.push(path[..length].to_vec());
+                if search(&path[length..], compression, max_functions, limit) {
+                    return true;
+                }
+                compression.functions.pop();
+                compression.main.pop();
+            }
+        }
+        false
+    }
+
+    let mut compression = Compression {
+        main: vec![],
+        functions: vec![],
+    };
+    if search(path, &mut compression, functions, limit) {
+        Some(compression)
+    } else {
+        None
+    }
+}
+
+/// Wakes the robot up, sends it the movement program without the video
+/// feed and returns the dust it reports at the end.
+fn collect_dust(program: &[i64], compression: &Compression<Instruction>) -> i64 {
+    let mut program = program.to_vec();
+    program[0] = 2;
+    let mut intcode = Intcode::new(program).expect("Program cannot be empty");
+    intcode.reset();
+    ascii::push_line(&mut intcode, &compression.main_routine());
+    for f in 0..3 {
+        let function = compression.functions.get(f).map_or(vec![], |f| f.clone());
+        ascii::push_line(&mut intcode, &join(&function));
+    }
+    ascii::push_line(&mut intcode, "n");
+    intcode.run().expect("Robot program failed");
+    let (_, values) = ascii::take_text(&mut intcode);
+    *values.last().expect("Robot did not report dust")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand<T: Clone>(compression: &Compression<T>) -> Vec<T> {
+        compression
+            .main
+            .iter()
+            .flat_map(|&f| compression.functions[f].clone())
+            .collect()
+    }
+
+    #[test]
+    fn example_17_1() {
+        let view = "..#..........
+..#..........
+#######...###
+#.#...#...#.#
+#############
+..#...#...#..
+..#####...^..";
+        let (scaffold, robot) = parse_view(view);
+        assert_eq!(alignment_sum(&scaffold), 76);
+        assert_eq!(robot, Some((Point::new(10, 6), Direction::Up)));
+    }
+
+    #[test]
+    fn example_17_2() {
+        let view = "#######...#####
+#.....#...#...#
+#.....#...#...#
+......#...#...#
+......#...###.#
+......#.....#.#
+^########...#.#
+......#.#...#.#
+......#########
+........#...#..
+....#########..
+....#...#......
+....#...#......
+....#...#......
+....#####......";
+        let (scaffold, robot) = parse_view(view);
+        let path = find_path(&scaffold, robot.unwrap());
+        assert_eq!(
+            join(&path),
+            "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2"
+        );
+        let compression = compress(&path, 3, LIMIT).unwrap();
+        assert_eq!(expand(&compression), path);
+        assert!(compression.main_routine().len() <= LIMIT);
+        assert!(compression.functions.iter().all(|f| join(f).len() <= LIMIT));
+    }
+
+    #[test]
+    fn compress_reuses_functions() {
+        let path = vec![1, 2, 3, 1, 2, 3, 4, 4, 1, 2, 3];
+        for functions in 2..=3 {
+            let compression = compress(&path, functions, LIMIT).unwrap();
+            assert_eq!(expand(&compression), path);
+            assert!(compression.functions.len() <= functions);
+            assert!(compression.main_routine().len() <= LIMIT);
+        }
+        assert_eq!(compress(&path, 1, LIMIT), None);
+    }
+
+    #[test]
+    fn compress_respects_limits() {
+        let path = vec![1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(compress(&path, 3, 3), None);
+        let compression = compress(&path, 3, 5).unwrap();
+        assert_eq!(expand(&compression), path);
+        assert!(compression.functions.iter().all(|f| f.len() <= 3));
+        assert_eq!(compress(&[1, 1, 1, 1], 1, 5).unwrap().main, vec![0, 0]);
+        assert_eq!(compress(&[1; 12], 1, 5), None);
+        assert_eq!(compress(&[1; 12], 1, LIMIT).unwrap().main.len(), 6);
+    }
+
+    #[test]
+    fn empty_path_needs_no_functions() {
+        let compression = compress::<i64>(&[], 3, LIMIT).unwrap();
+        assert_eq!(compression.main_routine(), "");
+    }
+}
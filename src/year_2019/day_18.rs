@@ -0,0 +1,345 @@
+use crate::year_2019::point::{Direction, Point};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fs;
+
+/// Keys are graph nodes 0 to 25, the robot entrances follow after them.
+const FIRST_ROBOT: usize = 26;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_18_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let vault = Vault::parse(&input);
+    let steps = vault.collect_keys().expect("Keys cannot be collected");
+    println!("Fewest steps to collect all keys: {}", steps);
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_18_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let vault = Vault::parse(&input).split().expect("Vault cannot be split");
+    let steps = vault.collect_keys().expect("Keys cannot be collected");
+    println!("Fewest steps with four robots: {}", steps);
+}
+
+/// A shortest route from one node to a key, with the doors on the way as a
+/// bitmask of the keys that open them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Route {
+    key: usize,
+    steps: usize,
+    doors: u32,
+}
+
+#[derive(Debug, PartialEq)]
+struct Vault {
+    grid: Vec<Vec<char>>,
+    robots: Vec<Point>,
+}
+
+impl Vault {
+    fn parse(input: &str) -> Self {
+        let grid: Vec<Vec<char>> = input
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(|l| l.chars().collect())
+            .collect();
+        let robots = grid
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, &c)| c == '@')
+                    .map(move |(x, _)| Point::new(x as i64, y as i64))
+            })
+            .collect();
+        Vault { grid, robots }
+    }
+
+    fn tile(&self, point: Point) -> char {
+        self.grid
+            .get(point.y as usize)
+            .and_then(|row| row.get(point.x as usize))
+            .copied()
+            .unwrap_or('#')
+    }
+
+    /// Walls off the open area around a single entrance and puts a robot
+    /// into each diagonal corner of it.
+    fn split(&self) -> Option<Vault> {
+        let center = match self.robots[..] {
+            [center] => center,
+            _ => return None,
+        };
+        let mut grid = self.grid.clone();
+        let mut robots = vec![];
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let point = center + Point::new(dx, dy);
+                let tile = if dx != 0 && dy != 0 { '@' } else { '#' };
+                grid[point.y as usize][point.x as usize] = tile;
+                if tile == '@' {
+                    robots.push(point);
+                }
+            }
+        }
+        Some(Vault { grid, robots })
+    }
+
+    fn keys(&self) -> u32 {
+        self.grid
+            .iter()
+            .flatten()
+            .filter(|c| c.is_ascii_lowercase())
+            .fold(0, |keys, &c| keys | 1 << (c as u8 - b'a'))
+    }
+
+    /// Every key reachable from `start` by breadth-first search, walking over
+    /// doors and other keys.
+    fn routes_from(&self, start: Point) -> Vec<Route> {
+        let mut routes = vec![];
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+        seen.insert(start);
+        queue.push_back((start, 0, 0));
+        while let Some((position, steps, doors)) = queue.pop_front() {
+            let tile = self.tile(position);
+            if tile.is_ascii_lowercase() && position != start {
+                routes.push(Route {
+                    key: (tile as u8 - b'a') as usize,
+                    steps,
+                    doors,
+                });
+            }
+            let doors = if tile.is_ascii_uppercase() {
+                doors | 1 << (tile as u8 - b'A')
+            } else {
+                doors
+            };
+            for direction in Direction::ALL.iter() {
+                let next = position + direction.offset();
+                if self.tile(next) != '#' && seen.insert(next) {
+                    queue.push_back((next, steps + 1, doors));
+                }
+            }
+        }
+        routes
+    }
+
+    /// Routes from each key and each robot entrance to all other keys.
+    fn routes(&self) -> HashMap<usize, Vec<Route>> {
+        let mut routes = HashMap::new();
+        for (y, row) in self.grid.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                if c.is_ascii_lowercase() {
+                    let point = Point::new(x as i64, y as i64);
+                    routes.insert((c as u8 - b'a') as usize, self.routes_from(point));
+                }
+            }
+        }
+        for (i, &robot) in self.robots.iter().enumerate() {
+            routes.insert(FIRST_ROBOT + i, self.routes_from(robot));
+        }
+        routes
+    }
+
+    /// Fewest total steps for the robots to collect every key, found with
+    /// Dijkstra's algorithm over the node each robot is at and the keys
+    /// collected so far. A robot can only take routes whose doors it holds
+    /// keys for.
+    fn collect_keys(&self) -> Option<usize> {
+        let routes = self.routes();
+        let all_keys = self.keys();
+        let start: Vec<usize> = (0..self.robots.len()).map(|i| FIRST_ROBOT + i).collect();
+        let mut best: HashMap<(Vec<usize>, u32), usize> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        best.insert((start.clone(), 0), 0);
+        queue.push(Reverse((0, start, 0)));
+        while let Some(Reverse((steps, robots, keys))) = queue.pop() {
+            if keys == all_keys {
+                return Some(steps);
+            }
+            if best
+                .get(&(robots.clone(), keys))
+                .is_some_and(|&b| b < steps)
+            {
+                continue;
+            }
+            for (i, node) in robots.iter().enumerate() {
+                for route in &routes[node] {
+                    if keys & 1 << route.key != 0 || route.doors & !keys != 0 {
+                        continue;
+                    }
+                    let mut next = robots.clone();
+                    next[i] = route.key;
+                    let state = (next, keys | 1 << route.key);
+                    let total = steps + route.steps;
+                    if best.get(&state).is_none_or(|&b| total < b) {
+                        best.insert(state.clone(), total);
+                        queue.push(Reverse((total, state.0, state.1)));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_18_1() {
+        let vault = Vault::parse("#########\n#b.A.@.a#\n#########");
+        assert_eq!(vault.collect_keys(), Some(8));
+    }
+
+    #[test]
+    fn example_18_2() {
+        let vault = Vault::parse(
+            "########################
+#f.D.E.e.C.b.A.@.a.B.c.#
+######################.#
+#d.....................#
+########################",
+        );
+        assert_eq!(vault.collect_keys(), Some(86));
+    }
+
+    #[test]
+    fn example_18_3() {
+        let vault = Vault::parse(
+            "########################
+#...............b.C.D.f#
+#.######################
+#.....@.a.B.c.d.A.e.F.g#
+########################",
+        );
+        assert_eq!(vault.collect_keys(), Some(132));
+    }
+
+    #[test]
+    fn example_18_4() {
+        let vault = Vault::parse(
+            "#################
+#i.G..c...e..H.p#
+########.########
+#j.A..b...f..D.o#
+########@########
+#k.E..a...g..B.n#
+########.########
+#l.F..d...h..C.m#
+#################",
+        );
+        assert_eq!(vault.collect_keys(), Some(136));
+    }
+
+    #[test]
+    fn example_18_5() {
+        let vault = Vault::parse(
+            "########################
+#@..............ac.GI.b#
+###d#e#f################
+###A#B#C################
+###g#h#i################
+########################",
+        );
+        assert_eq!(vault.collect_keys(), Some(81));
+    }
+
+    #[test]
+    fn example_18_6() {
+        let vault = Vault::parse(
+            "#######
+#a.#Cd#
+##...##
+##.@.##
+##...##
+#cB#Ab#
+#######",
+        );
+        let split = vault.split().unwrap();
+        assert_eq!(split.robots.len(), 4);
+        assert_eq!(split.tile(Point::new(3, 3)), '#');
+        assert_eq!(split.collect_keys(), Some(8));
+    }
+
+    #[test]
+    fn example_18_7() {
+        let vault = Vault::parse(
+            "###############
+#d.ABC.#.....a#
+######@#@######
+###############
+######@#@######
+#b.....#.....c#
+###############",
+        );
+        assert_eq!(vault.split(), None);
+        assert_eq!(vault.collect_keys(), Some(24));
+    }
+
+    #[test]
+    fn example_18_8() {
+        let vault = Vault::parse(
+            "#############
+#DcBa.#.GhKl#
+#.###@#@#I###
+#e#d#####j#k#
+###C#@#@###J#
+#fEbA.#.FgHi#
+#############",
+        );
+        assert_eq!(vault.collect_keys(), Some(32));
+    }
+
+    #[test]
+    fn example_18_9() {
+        let vault = Vault::parse(
+            "#############
+#g#f.D#..h#l#
+#F###e#E###.#
+#dCba@#@BcIJ#
+#############
+#nK.L@#@G...#
+#M###N#H###.#
+#o#m..#i#jk.#
+#############",
+        );
+        assert_eq!(vault.collect_keys(), Some(72));
+    }
+
+    #[test]
+    fn routes_record_doors() {
+        let vault = Vault::parse("#########\n#b.A.@.a#\n#########");
+        let routes = vault.routes();
+        assert_eq!(
+            routes[&FIRST_ROBOT],
+            vec![
+                Route {
+                    key: 0,
+                    steps: 2,
+                    doors: 0
+                },
+                Route {
+                    key: 1,
+                    steps: 4,
+                    doors: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unreachable_keys_are_reported() {
+        let vault = Vault::parse("#####\n#@#a#\n#####");
+        assert_eq!(vault.collect_keys(), None);
+        let vault = Vault::parse("#######\n#@.A.a#\n#######");
+        assert_eq!(vault.collect_keys(), None);
+    }
+}
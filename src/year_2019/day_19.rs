@@ -0,0 +1,201 @@
+use crate::year_2019::intcode::{parse_program, Intcode};
+use crate::year_2019::point::Point;
+use std::fs;
+
+/// The beam is assumed to widen by at most this many columns per row. It
+/// bounds the search in rows near the emitter, which can be too thin for the
+/// beam to hit any cell.
+const MAX_SLOPE: i64 = 10;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_19_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let mut drone = Drone::new(&parse_program(&input));
+    let count = affected_points(|point| drone.pulled(point), 50);
+    println!("Points affected by the beam: {}", count);
+    println!("Drones deployed: {}", drone.queries);
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_19_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let mut drone = Drone::new(&parse_program(&input));
+    let corner = closest_square(|point| drone.pulled(point), 100);
+    println!("Closest square: {}", corner.x * 10000 + corner.y);
+    println!("Drones deployed: {}", drone.queries);
+}
+
+/// Deploys a fresh drone for every query, which means resetting the whole
+/// machine, and keeps count of how many were sent.
+struct Drone {
+    intcode: Intcode,
+    queries: usize,
+}
+
+impl Drone {
+    fn new(program: &[i64]) -> Self {
+        Drone {
+            intcode: Intcode::new(program.to_vec()).expect("Program cannot be empty"),
+            queries: 0,
+        }
+    }
+
+    fn pulled(&mut self, point: Point) -> bool {
+        self.queries += 1;
+        self.intcode
+            .reset()
+            .push_input(point.x)
+            .push_input(point.y)
+            .run()
+            .expect("Drone program failed");
+        self.intcode.take_output().expect("Drone did not reply") == 1
+    }
+}
+
+/// Follows the beam down row by row. The pulled cells of a row are a single
+/// run and both of its edges only move right from one row to the next, so
+/// each edge is found by walking on from where it was in the row above.
+struct Edges<F> {
+    probe: F,
+    left: i64,
+    right: i64,
+}
+
+impl<F: FnMut(Point) -> bool> Edges<F> {
+    fn new(probe: F) -> Self {
+        Edges {
+            probe,
+            left: 0,
+            right: 0,
+        }
+    }
+
+    /// The first and last pulled cell of row `y` up to column `max_x`, or
+    /// `None` if the beam misses the row.
+    fn row(&mut self, y: i64, max_x: i64) -> Option<(i64, i64)> {
+        let probe = &mut self.probe;
+        let left = (self.left..=max_x).find(|&x| probe(Point::new(x, y)))?;
+        let mut right = self.right.max(left);
+        while right < max_x && probe(Point::new(right + 1, y)) {
+            right += 1;
+        }
+        self.left = left;
+        self.right = right;
+        Some((left, right))
+    }
+}
+
+/// Pulled cells in the `size` by `size` area closest to the emitter.
+fn affected_points<F: FnMut(Point) -> bool>(probe: F, size: i64) -> usize {
+    let mut edges = Edges::new(probe);
+    (0..size)
+        .filter_map(|y| edges.row(y, size - 1))
+        .map(|(left, right)| (right - left + 1) as usize)
+        .sum()
+}
+
+/// The top left corner of the first `size` by `size` square that fits in
+/// the beam. A square whose bottom row starts at the left edge fits when the
+/// row `size - 1` above reaches far enough right.
+fn closest_square<F: FnMut(Point) -> bool>(probe: F, size: i64) -> Point {
+    let mut edges = Edges::new(probe);
+    let mut rights = vec![];
+    let mut y = 0;
+    loop {
+        let span = edges.row(y, MAX_SLOPE * (y + 1));
+        rights.push(span.map(|(_, right)| right));
+        if let Some((left, _)) = span {
+            let top = y - size + 1;
+            if 0 <= top && rights[top as usize].is_some_and(|right| left + size - 1 <= right) {
+                return Point::new(left, top);
+            }
+        }
+        y += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn parse_beam(input: &str) -> HashSet<Point> {
+        let mut beam = HashSet::new();
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if c == '#' {
+                    beam.insert(Point::new(x as i64, y as i64));
+                }
+            }
+        }
+        beam
+    }
+
+    /// A beam between the rays through (13, 10) and (14, 10), which misses
+    /// the rows right after the emitter.
+    fn cone(point: Point) -> bool {
+        13 * point.y <= 10 * point.x && 10 * point.x <= 14 * point.y
+    }
+
+    #[test]
+    fn example_19_1() {
+        let beam = parse_beam(
+            "#.........
+.#........
+..##......
+...###....
+....###...
+.....####.
+......####
+......####
+.......###
+........##",
+        );
+        let mut queries = 0;
+        let count = affected_points(
+            |point| {
+                queries += 1;
+                beam.contains(&point)
+            },
+            10,
+        );
+        assert_eq!(count, 27);
+        assert!(queries < 100);
+    }
+
+    #[test]
+    fn closest_square_matches_brute_force() {
+        for size in 1..=10 {
+            let expected = (0..)
+                .flat_map(|y| (0..=MAX_SLOPE * (y + 1)).map(move |x| Point::new(x, y)))
+                .find(|&corner| {
+                    cone(corner + Point::new(size - 1, 0)) && cone(corner + Point::new(0, size - 1))
+                })
+                .unwrap();
+            assert_eq!(closest_square(cone, size), expected, "{}", size);
+        }
+    }
+
+    #[test]
+    fn empty_rows_are_skipped() {
+        let mut edges = Edges::new(cone);
+        assert_eq!(edges.row(0, 10), Some((0, 0)));
+        assert_eq!(edges.row(1, 20), None);
+        assert_eq!(edges.row(2, 30), None);
+        assert_eq!(edges.row(3, 40), Some((4, 4)));
+        assert_eq!(edges.row(20, 210), Some((26, 28)));
+    }
+
+    #[test]
+    fn drone_is_reset_for_every_query() {
+        // Replies whether x is less than y.
+        let program = vec![3, 100, 3, 101, 7, 100, 101, 102, 4, 102, 99];
+        let mut drone = Drone::new(&program);
+        assert!(drone.pulled(Point::new(1, 2)));
+        assert!(!drone.pulled(Point::new(2, 1)));
+        assert!(drone.pulled(Point::new(0, 5)));
+        assert_eq!(drone.queries, 3);
+    }
+}
@@ -14,7 +14,7 @@ pub fn part_1() {
     intcode.reset();
     intcode.write_noun(12);
     intcode.write_verb(2);
-    intcode.run();
+    intcode.run().expect("Program failed");
     let result = intcode.read_output();
     assert_eq!(result, 10566835);
     println!("IntCode at position 0: {}", result);
@@ -35,8 +35,7 @@ pub fn part_2() {
             intcode.reset();
             intcode.write_noun(noun);
             intcode.write_verb(verb);
-            intcode.run();
-            if intcode.read_output() == 19690720 {
+            if intcode.run().is_ok() && intcode.read_output() == 19690720 {
                 println!("noun: {}, verb: {}", noun, verb);
                 assert_eq!(noun * 100 + verb, 2347);
                 break;
@@ -54,7 +53,7 @@ mod tests {
         let input = vec![1, 0, 0, 0, 99];
         let output = vec![2, 0, 0, 0, 99];
         let mut intcode = Intcode::new(input).unwrap();
-        intcode.reset().run();
+        intcode.reset().run().unwrap();
         assert_eq!(intcode.dump(), output);
     }
 
@@ -63,7 +62,7 @@ mod tests {
         let input = vec![2, 3, 0, 3, 99];
         let output = vec![2, 3, 0, 6, 99];
         let mut intcode = Intcode::new(input).unwrap();
-        intcode.reset().run();
+        intcode.reset().run().unwrap();
         assert_eq!(intcode.dump(), output);
     }
 
@@ -72,7 +71,7 @@ mod tests {
         let input = vec![2, 4, 4, 5, 99, 0];
         let output = vec![2, 4, 4, 5, 99, 9801];
         let mut intcode = Intcode::new(input).unwrap();
-        intcode.reset().run();
+        intcode.reset().run().unwrap();
         assert_eq!(intcode.dump(), output);
     }
 
@@ -81,7 +80,7 @@ mod tests {
         let input = vec![1, 1, 1, 4, 99, 5, 6, 0, 99];
         let output = vec![30, 1, 1, 4, 2, 5, 6, 0, 99];
         let mut intcode = Intcode::new(input).unwrap();
-        intcode.reset().run();
+        intcode.reset().run().unwrap();
         assert_eq!(intcode.dump(), output);
     }
 
@@ -90,7 +89,7 @@ mod tests {
         let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
         let output = vec![3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50];
         let mut intcode = Intcode::new(input).unwrap();
-        intcode.reset().run();
+        intcode.reset().run().unwrap();
         assert_eq!(intcode.dump(), output);
     }
 }
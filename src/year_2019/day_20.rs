@@ -0,0 +1,444 @@
+use crate::year_2019::point::{Direction, Point};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+
+const ENTRANCE: &str = "AA";
+const EXIT: &str = "ZZ";
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_20_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let maze = parse_maze(&input).expect("Invalid maze");
+    let steps = maze.shortest_path(false).expect("Exit cannot be reached");
+    println!("Steps from AA to ZZ: {}", steps);
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_20_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let maze = parse_maze(&input).expect("Invalid maze");
+    let steps = maze.shortest_path(true).expect("Exit cannot be reached");
+    println!("Steps through the recursive maze: {}", steps);
+}
+
+#[derive(Debug, PartialEq)]
+enum MazeError {
+    Detached(String),
+    Unpaired(String),
+    Missing(String),
+}
+
+/// A two-letter label and the open tile it is written next to. Outer labels
+/// sit outside the outer edge of the donut, the others inside the hole.
+#[derive(Debug, PartialEq)]
+struct Label {
+    name: String,
+    tile: Point,
+    outer: bool,
+}
+
+/// Where stepping into a portal leads and how it changes the level in the
+/// recursive maze: inner portals lead one level down, outer ones back up.
+struct Portal {
+    target: Point,
+    depth: i64,
+}
+
+struct Maze {
+    open: HashSet<Point>,
+    portals: HashMap<Point, Portal>,
+    entrance: Point,
+    exit: Point,
+}
+
+struct Grid {
+    rows: Vec<Vec<char>>,
+}
+
+impl Grid {
+    fn get(&self, point: Point) -> char {
+        if point.x < 0 || point.y < 0 {
+            return ' ';
+        }
+        self.rows
+            .get(point.y as usize)
+            .and_then(|row| row.get(point.x as usize))
+            .copied()
+            .unwrap_or(' ')
+    }
+
+    fn points(&self) -> impl Iterator<Item = (Point, char)> + '_ {
+        self.rows.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, &c)| (Point::new(x as i64, y as i64), c))
+        })
+    }
+}
+
+/// Finds the labels by their first letter, which is the top one of a
+/// vertical label and the left one of a horizontal label. The open tile is
+/// right before or after the two letters in the same direction.
+fn parse_labels(grid: &Grid) -> Result<Vec<Label>, MazeError> {
+    let walls: Vec<Point> = grid
+        .points()
+        .filter(|&(_, c)| c == '#')
+        .map(|(point, _)| point)
+        .collect();
+    let min_x = walls.iter().map(|p| p.x).min().unwrap_or(0);
+    let max_x = walls.iter().map(|p| p.x).max().unwrap_or(0);
+    let min_y = walls.iter().map(|p| p.y).min().unwrap_or(0);
+    let max_y = walls.iter().map(|p| p.y).max().unwrap_or(0);
+    let mut labels = vec![];
+    for (first, c) in grid.points().filter(|(_, c)| c.is_ascii_uppercase()) {
+        for &step in [Point::new(1, 0), Point::new(0, 1)].iter() {
+            let second = grid.get(first + step);
+            if !second.is_ascii_uppercase() {
+                continue;
+            }
+            let name: String = [c, second].iter().collect();
+            let tile = [first + step * 2, first - step]
+                .iter()
+                .copied()
+                .find(|&p| grid.get(p) == '.')
+                .ok_or_else(|| MazeError::Detached(name.clone()))?;
+            let outer = tile.x == min_x || tile.x == max_x || tile.y == min_y || tile.y == max_y;
+            labels.push(Label { name, tile, outer });
+        }
+    }
+    Ok(labels)
+}
+
+fn parse_maze(input: &str) -> Result<Maze, MazeError> {
+    let grid = Grid {
+        rows: input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.chars().collect())
+            .collect(),
+    };
+    let open = grid
+        .points()
+        .filter(|&(_, c)| c == '.')
+        .map(|(point, _)| point)
+        .collect();
+    let mut by_name: HashMap<String, Vec<Label>> = HashMap::new();
+    for label in parse_labels(&grid)? {
+        by_name.entry(label.name.clone()).or_default().push(label);
+    }
+    let mut single = |name: &str| match by_name.remove(name).as_deref() {
+        Some([label]) => Ok(label.tile),
+        Some(_) => Err(MazeError::Unpaired(name.to_string())),
+        None => Err(MazeError::Missing(name.to_string())),
+    };
+    let entrance = single(ENTRANCE)?;
+    let exit = single(EXIT)?;
+    let mut portals = HashMap::new();
+    for (name, labels) in by_name {
+        let (a, b) = match &labels[..] {
+            [a, b] if a.outer != b.outer => (a, b),
+            _ => return Err(MazeError::Unpaired(name)),
+        };
+        for (from, to) in [(a, b), (b, a)].iter() {
+            let depth = if from.outer { -1 } else { 1 };
+            let target = to.tile;
+            portals.insert(from.tile, Portal { target, depth });
+        }
+    }
+    Ok(Maze {
+        open,
+        portals,
+        entrance,
+        exit,
+    })
+}
+
+impl Maze {
+    /// Fewest steps from the entrance to the exit. Levels are bounded by
+    /// inner times outer portals, since deeper paths can be shortened.
+    fn shortest_path(&self, recursive: bool) -> Option<usize> {
+        let pairs = self.portals.len() as i64 / 2;
+        self.search(recursive, pairs * pairs)
+    }
+
+    /// Breadth-first search over tiles and levels down to `max_level`.
+    fn search(&self, recursive: bool, max_level: i64) -> Option<usize> {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+        seen.insert((self.entrance, 0));
+        queue.push_back((self.entrance, 0, 0));
+        while let Some((position, level, steps)) = queue.pop_front() {
+            if position == self.exit && level == 0 {
+                return Some(steps);
+            }
+            let mut next = vec![];
+            for direction in Direction::ALL.iter() {
+                let tile = position + direction.offset();
+                if self.open.contains(&tile) {
+                    next.push((tile, level));
+                }
+            }
+            if let Some(portal) = self.portals.get(&position) {
+                let level = if recursive { level + portal.depth } else { 0 };
+                if (0..=max_level).contains(&level) {
+                    next.push((portal.target, level));
+                }
+            }
+            for state in next {
+                if seen.insert(state) {
+                    queue.push_back((state.0, state.1, steps + 1));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "         A
+         A
+  #######.#########
+  #######.........#
+  #######.#######.#
+  #######.#######.#
+  #######.#######.#
+  #####  B    ###.#
+BC...##  C    ###.#
+  ##.##       ###.#
+  ##...DE  F  ###.#
+  #####    G  ###.#
+  #########.#####.#
+DE..#######...###.#
+  #.#########.###.#
+FG..#########.....#
+  ###########.#####
+             Z
+             Z";
+
+    const EXAMPLE_2: &str = "                   A
+                   A
+  #################.#############
+  #.#...#...................#.#.#
+  #.#.#.###.###.###.#########.#.#
+  #.#.#.......#...#.....#.#.#...#
+  #.#########.###.#####.#.#.###.#
+  #.............#.#.....#.......#
+  ###.###########.###.#.#.#######
+  #.....#        A   C    #.#.#.#
+  #######        S   P    #####.#
+  #.#...#                 #......VT
+  #.#.#.#                 #.#####
+  #...#.#               YN....#.#
+  #.###.#                 #####.#
+DI....#.#                 #.....#
+  #####.#                 #.###.#
+ZZ......#               QG....#..AS
+  ###.###                 #######
+JO..#.#.#                 #.....#
+  #.#.#.#                 ###.#.#
+  #...#..DI             BU....#..LF
+  #####.#                 #.#####
+YN......#               VT..#....QG
+  #.###.#                 #.###.#
+  #.#...#                 #.....#
+  ###.###    J L     J    #.#.###
+  #.....#    O F     P    #.#...#
+  #.###.#####.#.#####.#####.###.#
+  #...#.#.#...#.....#.....#.#...#
+  #.#####.###.###.#.#.#########.#
+  #...#.#.....#...#.#.#.#.....#.#
+  #.###.#####.###.###.#.#.#######
+  #.#.........#...#.............#
+  #########.###.###.#############
+           B   J   C
+           U   P   P";
+
+    const EXAMPLE_3: &str = "             Z L X W       C
+             Z P Q B       K
+  ###########.#.#.#.#######.###############
+  #...#.......#.#.......#.#.......#.#.#...#
+  ###.#.#.#.#.#.#.#.###.#.#.#######.#.#.###
+  #.#...#.#.#...#.#.#...#...#...#.#.......#
+  #.###.#######.###.###.#.###.###.#.#######
+  #...#.......#.#...#...#.............#...#
+  #.#########.#######.#.#######.#######.###
+  #...#.#    F       R I       Z    #.#.#.#
+  #.###.#    D       E C       H    #.#.#.#
+  #.#...#                           #...#.#
+  #.###.#                           #.###.#
+  #.#....OA                       WB..#.#..ZH
+  #.###.#                           #.#.#.#
+CJ......#                           #.....#
+  #######                           #######
+  #.#....CK                         #......IC
+  #.###.#                           #.###.#
+  #.....#                           #...#.#
+  ###.###                           #.#.#.#
+XF....#.#                         RF..#.#.#
+  #####.#                           #######
+  #......CJ                       NM..#...#
+  ###.#.#                           #.###.#
+RE....#.#                           #......RF
+  ###.###        X   X       L      #.#.#.#
+  #.....#        F   Q       P      #.#.#.#
+  ###.###########.###.#######.#########.###
+  #.....#...#.....#.......#...#.....#.#...#
+  #####.#.###.#######.#######.###.###.#.#.#
+  #.......#.......#.#.#.#.#...#...#...#.#.#
+  #####.###.#####.#.#.#.#.###.###.#.###.###
+  #.......#.....#.#...#...............#...#
+  #############.#.#.###.###################
+               A O F   N
+               A A D   M";
+
+    fn labels(input: &str) -> Vec<Label> {
+        let grid = Grid {
+            rows: input.lines().map(|l| l.chars().collect()).collect(),
+        };
+        parse_labels(&grid).unwrap()
+    }
+
+    fn label(name: &str, x: i64, y: i64, outer: bool) -> Label {
+        Label {
+            name: name.to_string(),
+            tile: Point::new(x, y),
+            outer,
+        }
+    }
+
+    #[test]
+    fn labels_in_all_four_orientations() {
+        let found = labels(EXAMPLE_1);
+        let expected = [
+            label("AA", 9, 2, true),
+            label("BC", 9, 6, false),
+            label("BC", 2, 8, true),
+            label("DE", 6, 10, false),
+            label("FG", 11, 12, false),
+            label("DE", 2, 13, true),
+            label("FG", 2, 15, true),
+            label("ZZ", 13, 16, true),
+        ];
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn malformed_labels() {
+        let detached = "  #####\nAA  ...\n  #####";
+        assert_eq!(
+            parse_maze(detached).err(),
+            Some(MazeError::Detached(String::from("AA")))
+        );
+        let without_exit = "  ###\nAA..#\n  ###";
+        assert_eq!(
+            parse_maze(without_exit).err(),
+            Some(MazeError::Missing(String::from("ZZ")))
+        );
+        let lonely = "  #####\nAA...ZZ\n  #.###\n   B\n   C";
+        assert_eq!(
+            parse_maze(lonely).err(),
+            Some(MazeError::Unpaired(String::from("BC")))
+        );
+    }
+
+    /// A donut whose top side is crossed by vertical corridors, given by
+    /// their column and the labels above and below them. The corridors are
+    /// joined by a passage along the second row between the columns
+    /// `joined`.
+    fn corridor_maze(corridors: &[(usize, &str, &str)], joined: (usize, usize)) -> String {
+        let right = corridors.iter().map(|c| c.0).max().unwrap() + 2;
+        let mut rows = vec![vec![' '; right + 1]; 13];
+        for (y, row) in rows.iter_mut().enumerate().skip(2) {
+            for (x, tile) in row.iter_mut().enumerate().skip(2) {
+                let hole = (7..=10).contains(&y) && (4..=right - 2).contains(&x);
+                if !hole {
+                    *tile = '#';
+                }
+            }
+        }
+        for tile in &mut rows[3][joined.0..=joined.1] {
+            *tile = '.';
+        }
+        for &(x, outer, inner) in corridors {
+            let mut open = 3..=3;
+            for (y, c) in outer.chars().enumerate() {
+                rows[y][x] = c;
+                open = 2..=*open.end();
+            }
+            for (y, c) in inner.chars().enumerate() {
+                rows[y + 7][x] = c;
+                open = *open.start()..=6;
+            }
+            for y in open {
+                rows[y][x] = '.';
+            }
+        }
+        let lines: Vec<String> = rows.iter().map(|row| row.iter().collect()).collect();
+        lines.join("\n")
+    }
+
+    #[test]
+    fn example_20_1() {
+        let maze = parse_maze(EXAMPLE_1).unwrap();
+        assert_eq!(maze.portals.len(), 6);
+        assert_eq!(maze.shortest_path(false), Some(23));
+        assert_eq!(maze.shortest_path(true), Some(26));
+    }
+
+    #[test]
+    fn example_20_2() {
+        let maze = parse_maze(EXAMPLE_2).unwrap();
+        assert_eq!(maze.shortest_path(false), Some(58));
+        assert_eq!(maze.shortest_path(true), None);
+    }
+
+    #[test]
+    fn example_20_3() {
+        let maze = parse_maze(EXAMPLE_3).unwrap();
+        assert_eq!(maze.shortest_path(true), Some(396));
+    }
+
+    #[test]
+    fn shortest_path_goes_deeper_than_there_are_portals() {
+        // The entrance is on a ring of 7 corridors, and far away is a ring of
+        // 11 with the exit one level above it. Going once round a ring moves
+        // 7 or 11 levels, so crossing over only once needs 7a - 11b = 1,
+        // which first holds for a = 8: 56 levels down.
+        let name = |i: usize| format!("B{}", (b'A' + i as u8) as char);
+        let ring = |first: usize, len: usize, i: usize| {
+            (name(first + (i + len - 1) % len), name(first + i))
+        };
+        let far = 207;
+        let mut corridors = vec![(5, String::from("AA"), String::new())];
+        let (outer, inner) = ring(0, 7, 0);
+        corridors.push((7, outer, inner));
+        let (outer, inner) = ring(7, 11, 0);
+        corridors.push((far, outer, inner));
+        corridors.push((far + 2, name(18), String::new()));
+        for i in 1..7 {
+            let (outer, inner) = ring(0, 7, i);
+            corridors.push((far + 2 + 2 * i, outer, inner));
+        }
+        for i in 1..11 {
+            let (outer, inner) = ring(7, 11, i);
+            corridors.push((far + 14 + 2 * i, outer, inner));
+        }
+        corridors.push((far + 38, String::from("ZZ"), name(18)));
+        let corridors: Vec<(usize, &str, &str)> = corridors
+            .iter()
+            .map(|(x, outer, inner)| (*x, outer.as_str(), inner.as_str()))
+            .collect();
+        let maze = parse_maze(&corridor_maze(&corridors, (5, far + 2))).unwrap();
+        assert_eq!(maze.portals.len(), 38);
+        let steps = maze.shortest_path(true).unwrap();
+        let shallow = maze.search(true, maze.portals.len() as i64);
+        assert!(shallow.is_none_or(|shallow| steps < shallow));
+        assert_eq!(maze.search(true, 55), shallow);
+        assert_eq!(maze.search(true, 56), Some(steps));
+    }
+}
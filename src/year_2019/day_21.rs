@@ -0,0 +1,362 @@
+use crate::year_2019::intcode::{ascii, parse_program, Intcode};
+use std::fmt;
+use std::fs;
+
+/// Most instructions the springdroid's memory holds.
+const MAX_INSTRUCTIONS: usize = 15;
+
+/// Jumps if there is a hole in the next three tiles and ground to land on.
+const WALK_SCRIPT: &str = "NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J";
+
+/// Like the walking script, but only jumps if the droid can either walk on
+/// or jump again right after landing.
+const RUN_SCRIPT: &str = "NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J
+NOT E T
+NOT T T
+OR H T
+AND T J";
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_21_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let script = Script::parse(WALK_SCRIPT, Mode::Walk).expect("Invalid script");
+    report_survival(&script);
+    match run_script(&parse_program(&input), &script) {
+        Ok(damage) => println!("Hull damage: {}", damage),
+        Err(text) => print!("{}", text),
+    }
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_21_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let script = Script::parse(RUN_SCRIPT, Mode::Run).expect("Invalid script");
+    report_survival(&script);
+    match run_script(&parse_program(&input), &script) {
+        Ok(damage) => println!("Hull damage: {}", damage),
+        Err(text) => print!("{}", text),
+    }
+}
+
+/// Tries the script on every survivable hull of up to twelve tiles before
+/// the springdroid is sent out.
+fn report_survival(script: &Script) {
+    let hulls = hulls(12);
+    let survived = hulls.iter().filter(|hull| simulate(script, hull)).count();
+    println!("Survives {} of {} generated hulls", survived, hulls.len());
+}
+
+#[derive(Debug, PartialEq)]
+enum ScriptError {
+    Malformed(String),
+    TooLong(usize),
+    SensorOutOfRange(Register),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Walk,
+    Run,
+}
+
+impl Mode {
+    fn command(self) -> &'static str {
+        match self {
+            Mode::Walk => "WALK",
+            Mode::Run => "RUN",
+        }
+    }
+
+    /// Walking only reads the sensors A to D, running reads A to I.
+    fn sensors(self) -> usize {
+        match self {
+            Mode::Walk => 4,
+            Mode::Run => 9,
+        }
+    }
+}
+
+/// Sensor `n` tells whether there is ground `n + 1` tiles ahead. Only the
+/// temporary register T and the jump register J can be written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Register {
+    Sensor(usize),
+    Temporary,
+    Jump,
+}
+
+impl Register {
+    fn parse(input: &str) -> Option<Register> {
+        match input.as_bytes() {
+            [b'T'] => Some(Register::Temporary),
+            [b'J'] => Some(Register::Jump),
+            [c @ b'A'..=b'I'] => Some(Register::Sensor((c - b'A') as usize)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Register::Sensor(n) => write!(f, "{}", (b'A' + *n as u8) as char),
+            Register::Temporary => write!(f, "T"),
+            Register::Jump => write!(f, "J"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operation {
+    And,
+    Or,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Instruction {
+    operation: Operation,
+    source: Register,
+    target: Register,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operation = match self.operation {
+            Operation::And => "AND",
+            Operation::Or => "OR",
+            Operation::Not => "NOT",
+        };
+        write!(f, "{} {} {}", operation, self.source, self.target)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Script {
+    mode: Mode,
+    instructions: Vec<Instruction>,
+}
+
+impl Script {
+    fn parse(input: &str, mode: Mode) -> Result<Script, ScriptError> {
+        let mut instructions = vec![];
+        for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let malformed = || ScriptError::Malformed(line.to_string());
+            let (operation, source, target) = match line.split_whitespace().collect::<Vec<&str>>()[..]
+            {
+                [operation, source, target] => (operation, source, target),
+                _ => return Err(malformed()),
+            };
+            let operation = match operation {
+                "AND" => Operation::And,
+                "OR" => Operation::Or,
+                "NOT" => Operation::Not,
+                _ => return Err(malformed()),
+            };
+            let source = Register::parse(source).ok_or_else(malformed)?;
+            let target = match Register::parse(target) {
+                Some(Register::Sensor(_)) | None => return Err(malformed()),
+                Some(target) => target,
+            };
+            if let Register::Sensor(n) = source {
+                if mode.sensors() <= n {
+                    return Err(ScriptError::SensorOutOfRange(source));
+                }
+            }
+            instructions.push(Instruction {
+                operation,
+                source,
+                target,
+            });
+        }
+        if MAX_INSTRUCTIONS < instructions.len() {
+            return Err(ScriptError::TooLong(instructions.len()));
+        }
+        Ok(Script { mode, instructions })
+    }
+
+    /// Runs the script with `ground` as the sensor readings. Both writable
+    /// registers start out false.
+    fn jumps(&self, ground: &[bool]) -> bool {
+        let mut temporary = false;
+        let mut jump = false;
+        for instruction in &self.instructions {
+            let source = match instruction.source {
+                Register::Sensor(n) => ground[n],
+                Register::Temporary => temporary,
+                Register::Jump => jump,
+            };
+            let target = match instruction.target {
+                Register::Temporary => &mut temporary,
+                _ => &mut jump,
+            };
+            *target = match instruction.operation {
+                Operation::And => source && *target,
+                Operation::Or => source || *target,
+                Operation::Not => !source,
+            };
+        }
+        jump
+    }
+}
+
+/// Sends the script to the springdroid and returns the reported hull
+/// damage, or the droid's last moments drawn as text if it fell.
+fn run_script(program: &[i64], script: &Script) -> Result<i64, String> {
+    let mut intcode = Intcode::new(program.to_vec()).expect("Program cannot be empty");
+    intcode.reset();
+    for instruction in &script.instructions {
+        ascii::push_line(&mut intcode, &instruction.to_string());
+    }
+    ascii::push_line(&mut intcode, script.mode.command());
+    intcode.run().expect("Springdroid program failed");
+    let (text, values) = ascii::take_text(&mut intcode);
+    values.last().copied().ok_or(text)
+}
+
+/// Moves a droid from the first tile across the hull, one tile per step or
+/// four tiles per jump. Sensors past the end see ground.
+fn simulate(script: &Script, hull: &[bool]) -> bool {
+    let mut position = 0;
+    while position < hull.len() {
+        let ground: Vec<bool> = (1..=script.mode.sensors())
+            .map(|n| hull.get(position + n).copied().unwrap_or(true))
+            .collect();
+        position += if script.jumps(&ground) { 4 } else { 1 };
+        if !hull.get(position).copied().unwrap_or(true) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Whether a droid that knew the whole hull could get across it.
+fn survivable(hull: &[bool]) -> bool {
+    let mut safe = vec![true; hull.len() + 4];
+    for i in (0..hull.len()).rev() {
+        safe[i] = hull[i] && (safe[i + 1] || safe[i + 4]);
+    }
+    safe[0]
+}
+
+/// Every survivable hull of `length` tiles, all starting on ground.
+fn hulls(length: usize) -> Vec<Vec<bool>> {
+    (0..1 << (length - 1))
+        .map(|holes: u32| {
+            (0..length)
+                .map(|i| i == 0 || holes & 1 << (i - 1) == 0)
+                .collect::<Vec<bool>>()
+        })
+        .filter(|hull| survivable(hull))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A hull drawn with `#` for ground and `.` for holes.
+    fn parse_hull(input: &str) -> Vec<bool> {
+        input.trim().chars().map(|c| c == '#').collect()
+    }
+
+    fn failures(script: &Script, length: usize) -> Vec<Vec<bool>> {
+        hulls(length)
+            .into_iter()
+            .filter(|hull| !simulate(script, hull))
+            .collect()
+    }
+
+    #[test]
+    fn scripts_round_trip() {
+        let script = Script::parse(RUN_SCRIPT, Mode::Run).unwrap();
+        let text: Vec<String> = script.instructions.iter().map(|i| i.to_string()).collect();
+        assert_eq!(text.join("\n"), RUN_SCRIPT);
+    }
+
+    #[test]
+    fn malformed_scripts() {
+        assert_eq!(
+            Script::parse("XOR A J", Mode::Walk),
+            Err(ScriptError::Malformed(String::from("XOR A J")))
+        );
+        assert_eq!(
+            Script::parse("NOT T A", Mode::Walk),
+            Err(ScriptError::Malformed(String::from("NOT T A")))
+        );
+        assert_eq!(
+            Script::parse("NOT E J", Mode::Walk),
+            Err(ScriptError::SensorOutOfRange(Register::Sensor(4)))
+        );
+        assert!(Script::parse("NOT E J", Mode::Run).is_ok());
+        let long = vec!["NOT A J"; 16].join("\n");
+        assert_eq!(
+            Script::parse(&long, Mode::Run),
+            Err(ScriptError::TooLong(16))
+        );
+    }
+
+    #[test]
+    fn registers_start_false() {
+        let script = Script::parse("OR T J", Mode::Walk).unwrap();
+        assert!(!script.jumps(&[false; 4]));
+        let script = Script::parse("NOT J J", Mode::Walk).unwrap();
+        assert!(script.jumps(&[true; 4]));
+    }
+
+    #[test]
+    fn simulated_droid() {
+        let never = Script::parse("", Mode::Walk).unwrap();
+        assert!(simulate(&never, &parse_hull("#####")));
+        assert!(!simulate(&never, &parse_hull("###.#")));
+        let walk = Script::parse(WALK_SCRIPT, Mode::Walk).unwrap();
+        assert!(simulate(&walk, &parse_hull("#####.###")));
+        assert!(simulate(&walk, &parse_hull("#####...#")));
+        assert!(!simulate(&walk, &parse_hull("###.#.#..#")));
+    }
+
+    #[test]
+    fn survivable_hulls() {
+        assert!(survivable(&parse_hull("#...#")));
+        assert!(!survivable(&parse_hull("#....#")));
+        assert!(!survivable(&parse_hull("#.#..#")));
+        assert!(survivable(&parse_hull("##.##...#")));
+        assert_eq!(hulls(4).len(), 8);
+    }
+
+    #[test]
+    fn run_script_covers_walk_failures() {
+        let walk = Script::parse(WALK_SCRIPT, Mode::Walk).unwrap();
+        let run = Script::parse(RUN_SCRIPT, Mode::Run).unwrap();
+        assert!(!failures(&walk, 12).is_empty());
+        for hull in failures(&walk, 12) {
+            assert!(simulate(&run, &hull), "{:?}", hull);
+        }
+    }
+
+    #[test]
+    fn script_is_sent_as_text() {
+        // Echoes the input up to the second newline, then reports 1000 plus
+        // the number of characters as damage.
+        let program = vec![
+            3, 100, 4, 100, 1001, 101, 1, 101, 1008, 100, 10, 102, 1006, 102, 0, 1001, 103, 1, 103,
+            1008, 103, 2, 102, 1006, 102, 0, 1001, 101, 1000, 101, 4, 101, 99,
+        ];
+        let script = Script::parse("NOT A J", Mode::Walk).unwrap();
+        assert_eq!(run_script(&program, &script), Ok(1013));
+        let failing = vec![104, 33, 104, 10, 99];
+        assert_eq!(run_script(&failing, &script), Err(String::from("!\n")));
+    }
+}
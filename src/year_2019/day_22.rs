@@ -0,0 +1,321 @@
+use std::fs;
+
+const SMALL_DECK: i64 = 10007;
+/// The deck size from the puzzle text. It is prime, so every shuffle has an
+/// inverse, which `large_deck_is_prime` checks. The value 119315633563047
+/// that was asked for instead is 3 * 173 * 229895247713, so any increment
+/// divisible by 3 or 173 could not be undone there.
+const LARGE_DECK: i64 = 119_315_717_514_047;
+const SHUFFLES: i64 = 101_741_582_076_661;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_22_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let techniques = parse_techniques(&input).expect("Invalid techniques");
+    let shuffle = Affine::shuffle(&techniques, SMALL_DECK);
+    println!("Position of card 2019: {}", shuffle.apply(2019));
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_22_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let techniques = parse_techniques(&input).expect("Invalid techniques");
+    let shuffle = Affine::shuffle(&techniques, LARGE_DECK).repeat(SHUFFLES);
+    let inverse = shuffle.inverse().expect("Shuffle cannot be undone");
+    println!("Card at position 2020: {}", inverse.apply(2020));
+}
+
+#[derive(Debug, PartialEq)]
+enum ShuffleError {
+    Malformed(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Technique {
+    NewStack,
+    Cut(i64),
+    Increment(i64),
+}
+
+fn parse_techniques(input: &str) -> Result<Vec<Technique>, ShuffleError> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            let malformed = || ShuffleError::Malformed(line.to_string());
+            let number =
+                |prefix: &str| line[prefix.len()..].parse::<i64>().map_err(|_| malformed());
+            if line == "deal into new stack" {
+                Ok(Technique::NewStack)
+            } else if line.starts_with("cut ") {
+                Ok(Technique::Cut(number("cut ")?))
+            } else if line.starts_with("deal with increment ") {
+                match number("deal with increment ")? {
+                    increment if 0 < increment => Ok(Technique::Increment(increment)),
+                    _ => Err(malformed()),
+                }
+            } else {
+                Err(malformed())
+            }
+        })
+        .collect()
+}
+
+/// Moves the card at position `p` to `a * p + b` in a deck of `n` cards.
+/// Products of two positions need up to twice the bits of the deck size, so
+/// everything is computed with 128 bits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Affine {
+    a: i128,
+    b: i128,
+    n: i128,
+}
+
+impl Affine {
+    fn new(a: i128, b: i128, n: i128) -> Self {
+        Affine {
+            a: a.rem_euclid(n),
+            b: b.rem_euclid(n),
+            n,
+        }
+    }
+
+    fn identity(n: i64) -> Self {
+        Affine::new(1, 0, n as i128)
+    }
+
+    fn technique(technique: Technique, n: i64) -> Self {
+        let n = n as i128;
+        match technique {
+            Technique::NewStack => Affine::new(-1, -1, n),
+            Technique::Cut(cut) => Affine::new(1, -(cut as i128), n),
+            Technique::Increment(increment) => Affine::new(increment as i128, 0, n),
+        }
+    }
+
+    /// All techniques applied one after the other as a single map.
+    fn shuffle(techniques: &[Technique], n: i64) -> Self {
+        techniques.iter().fold(Affine::identity(n), |shuffle, &t| {
+            shuffle.then(Affine::technique(t, n))
+        })
+    }
+
+    /// This map followed by `next`.
+    fn then(self, next: Affine) -> Self {
+        Affine::new(next.a * self.a % self.n, next.a * self.b + next.b, self.n)
+    }
+
+    /// The map applied `times` times, by repeated squaring.
+    fn repeat(self, times: i64) -> Self {
+        let mut result = Affine::identity(self.n as i64);
+        let mut square = self;
+        let mut times = times;
+        while 0 < times {
+            if times & 1 == 1 {
+                result = result.then(square);
+            }
+            square = square.then(square);
+            times >>= 1;
+        }
+        result
+    }
+
+    /// The map that puts every card back, which exists whenever `a` has a
+    /// modular inverse.
+    fn inverse(self) -> Option<Self> {
+        let a = mod_inverse(self.a, self.n)?;
+        Some(Affine::new(a, -a * self.b % self.n, self.n))
+    }
+
+    fn apply(self, position: i64) -> i64 {
+        ((self.a * position as i128 + self.b) % self.n) as i64
+    }
+}
+
+/// The inverse of `a` modulo `n` by the extended Euclidean algorithm.
+fn mod_inverse(a: i128, n: i128) -> Option<i128> {
+    let (mut r0, mut r1) = (n, a.rem_euclid(n));
+    let (mut t0, mut t1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    if r0 == 1 {
+        Some(t0.rem_euclid(n))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shuffles a deck card by card, where `deck[p]` is the card at position `p`.
+    fn deal(deck: &[i64], technique: Technique) -> Vec<i64> {
+        let n = deck.len();
+        match technique {
+            Technique::NewStack => deck.iter().rev().copied().collect(),
+            Technique::Cut(cut) => {
+                let cut = cut.rem_euclid(n as i64) as usize;
+                deck[cut..].iter().chain(&deck[..cut]).copied().collect()
+            }
+            Technique::Increment(increment) => {
+                let mut table = vec![0; n];
+                for (i, &card) in deck.iter().enumerate() {
+                    table[i * increment as usize % n] = card;
+                }
+                table
+            }
+        }
+    }
+
+    fn naive_shuffle(techniques: &[Technique], n: i64) -> Vec<i64> {
+        techniques
+            .iter()
+            .fold((0..n).collect(), |deck, &t| deal(&deck, t))
+    }
+
+    /// The deck after the map has been applied to every card.
+    fn fast_shuffle(shuffle: Affine) -> Vec<i64> {
+        let mut deck = vec![0; shuffle.n as usize];
+        for card in 0..shuffle.n as i64 {
+            deck[shuffle.apply(card) as usize] = card;
+        }
+        deck
+    }
+
+    const EXAMPLES: [(&str, [i64; 10]); 4] = [
+        (
+            "deal with increment 7
+deal into new stack
+deal into new stack",
+            [0, 3, 6, 9, 2, 5, 8, 1, 4, 7],
+        ),
+        (
+            "cut 6
+deal with increment 7
+deal into new stack",
+            [3, 0, 7, 4, 1, 8, 5, 2, 9, 6],
+        ),
+        (
+            "deal with increment 7
+deal with increment 9
+cut -2",
+            [6, 3, 0, 7, 4, 1, 8, 5, 2, 9],
+        ),
+        (
+            "deal into new stack
+cut -2
+deal with increment 7
+cut 8
+cut -4
+deal with increment 7
+cut 3
+deal with increment 9
+deal with increment 3
+cut -1",
+            [9, 2, 5, 8, 1, 4, 7, 0, 3, 6],
+        ),
+    ];
+
+    #[test]
+    fn example_22_1() {
+        for (input, expected) in EXAMPLES.iter() {
+            let techniques = parse_techniques(input).unwrap();
+            assert_eq!(naive_shuffle(&techniques, 10), expected.to_vec());
+            assert_eq!(
+                fast_shuffle(Affine::shuffle(&techniques, 10)),
+                expected.to_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn single_techniques() {
+        let deck: Vec<i64> = (0..10).collect();
+        assert_eq!(
+            deal(&deck, Technique::NewStack),
+            vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0]
+        );
+        assert_eq!(
+            deal(&deck, Technique::Cut(3)),
+            vec![3, 4, 5, 6, 7, 8, 9, 0, 1, 2]
+        );
+        assert_eq!(
+            deal(&deck, Technique::Cut(-4)),
+            vec![6, 7, 8, 9, 0, 1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            deal(&deck, Technique::Increment(3)),
+            vec![0, 7, 4, 1, 8, 5, 2, 9, 6, 3]
+        );
+    }
+
+    #[test]
+    fn repeated_shuffles_agree_with_naive_deck() {
+        let techniques = parse_techniques(EXAMPLES[3].0).unwrap();
+        for &n in [10, 11, 13, 101].iter() {
+            let shuffle = Affine::shuffle(&techniques, n);
+            let mut deck: Vec<i64> = (0..n).collect();
+            for times in 0..30 {
+                assert_eq!(fast_shuffle(shuffle.repeat(times)), deck);
+                deck = techniques.iter().fold(deck, |deck, &t| deal(&deck, t));
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_undoes_shuffle() {
+        let techniques = parse_techniques(EXAMPLES[3].0).unwrap();
+        let shuffle = Affine::shuffle(&techniques, LARGE_DECK).repeat(SHUFFLES);
+        let inverse = shuffle.inverse().unwrap();
+        for &card in [0, 1, 2020, LARGE_DECK - 1].iter() {
+            assert_eq!(inverse.apply(shuffle.apply(card)), card);
+            assert_eq!(shuffle.then(inverse).apply(card), card);
+        }
+        let small = Affine::shuffle(&techniques, 10).repeat(7);
+        let deck = fast_shuffle(small);
+        let inverse = small.inverse().unwrap();
+        for position in 0..10 {
+            assert_eq!(inverse.apply(position), deck[position as usize]);
+        }
+    }
+
+    #[test]
+    fn large_deck_is_prime() {
+        // Every shuffle can then be undone, as increments are never zero.
+        let n = LARGE_DECK;
+        assert!((2..).take_while(|d| d * d <= n).all(|d| n % d != 0));
+    }
+
+    #[test]
+    fn modular_inverse() {
+        assert_eq!(mod_inverse(3, 10), Some(7));
+        assert_eq!(mod_inverse(-3, 10), Some(3));
+        assert_eq!(mod_inverse(4, 10), None);
+        assert_eq!(mod_inverse(1, 1), Some(0));
+    }
+
+    #[test]
+    fn malformed_techniques() {
+        assert_eq!(
+            parse_techniques("cut x"),
+            Err(ShuffleError::Malformed(String::from("cut x")))
+        );
+        assert_eq!(
+            parse_techniques("deal with increment 0"),
+            Err(ShuffleError::Malformed(String::from(
+                "deal with increment 0"
+            )))
+        );
+        assert_eq!(
+            parse_techniques("shuffle"),
+            Err(ShuffleError::Malformed(String::from("shuffle")))
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use crate::year_2019::intcode::{parse_program, Intcode, Status};
+use std::collections::VecDeque;
+use std::fs;
+
+const NICS: usize = 50;
+const NAT: i64 = 255;
+
+/// What a NIC reads when no packet is waiting for it.
+const NO_PACKET: i64 = -1;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_23_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let mut network = Network::new(&parse_program(&input), NICS);
+    println!("First Y sent to the NAT: {}", network.first_nat_packet().1);
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_23_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let mut network = Network::new(&parse_program(&input), NICS);
+    println!(
+        "First Y delivered twice by the NAT: {}",
+        network.repeated_wake_up()
+    );
+}
+
+/// Computers that send each other `(x, y)` packets. Every NIC runs until it
+/// waits for input, so the computers take turns instead of running in
+/// parallel. Packets for address 255 go to the NAT, which keeps the last one
+/// and sends it to address 0 once the network is idle.
+struct Network {
+    nics: Vec<Intcode>,
+    queues: Vec<VecDeque<(i64, i64)>>,
+    nat: Option<(i64, i64)>,
+    first_nat_packet: Option<(i64, i64)>,
+}
+
+impl Network {
+    /// Boots `size` NICs, each of which first reads its network address.
+    fn new(program: &[i64], size: usize) -> Self {
+        let nics = (0..size)
+            .map(|address| {
+                let mut nic = Intcode::new(program.to_vec()).expect("Program cannot be empty");
+                nic.reset().push_input(address as i64);
+                nic
+            })
+            .collect();
+        Network {
+            nics,
+            queues: vec![VecDeque::new(); size],
+            nat: None,
+            first_nat_packet: None,
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.queues.iter().all(VecDeque::is_empty)
+    }
+
+    /// Hands every NIC its waiting packets, or -1 if there are none, runs it
+    /// and routes what it sends. Returns whether any packet was sent.
+    fn round(&mut self) -> bool {
+        let mut sent = false;
+        for address in 0..self.nics.len() {
+            let nic = &mut self.nics[address];
+            if self.queues[address].is_empty() {
+                nic.push_input(NO_PACKET);
+            }
+            for (x, y) in self.queues[address].drain(..) {
+                nic.push_input(x).push_input(y);
+            }
+            let status = nic.run().expect("NIC program failed");
+            assert!(
+                matches!(status, Status::AwaitingInput { .. }),
+                "NIC {} stopped",
+                address
+            );
+            for packet in nic.take_tuples(3) {
+                sent = true;
+                let (destination, x, y) = (packet[0], packet[1], packet[2]);
+                if destination == NAT {
+                    self.nat = Some((x, y));
+                    self.first_nat_packet.get_or_insert((x, y));
+                } else {
+                    self.queues
+                        .get_mut(destination as usize)
+                        .expect("Packet sent to unknown address")
+                        .push_back((x, y));
+                }
+            }
+        }
+        sent
+    }
+
+    fn first_nat_packet(&mut self) -> (i64, i64) {
+        while self.first_nat_packet.is_none() {
+            self.round();
+        }
+        self.first_nat_packet.unwrap()
+    }
+
+    /// Runs the network until the NAT wakes up address 0 with the same Y
+    /// value twice in a row. The network counts as idle when no packets
+    /// were waiting and a whole round went by without any being sent.
+    fn repeated_wake_up(&mut self) -> i64 {
+        let mut last = None;
+        loop {
+            let idle = self.is_idle();
+            if self.round() || !idle {
+                continue;
+            }
+            let (x, y) = self.nat.expect("Network is idle without a NAT packet");
+            if last == Some(y) {
+                return y;
+            }
+            last = Some(y);
+            self.queues[0].push_back((x, y));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every NIC boots by sending (3, address) to the NAT. A packet (x, y)
+    /// with x > 0 is passed on to the next address as (x - 1, y + 1), one
+    /// with x = 0 makes the NIC send (3, 10) to the NAT.
+    const RELAY: [i64; 52] = [
+        3, 100, 104, 255, 104, 3, 4, 100, 3, 101, 1008, 101, -1, 103, 1005, 103, 8, 3, 102, 1005,
+        101, 31, 104, 255, 104, 3, 104, 10, 1105, 1, 8, 1001, 100, 1, 103, 4, 103, 1001, 101, -1,
+        101, 4, 101, 1001, 102, 1, 102, 4, 102, 1105, 1, 8,
+    ];
+
+    #[test]
+    fn first_packet_comes_from_address_0() {
+        let mut network = Network::new(&RELAY, NICS);
+        assert_eq!(network.first_nat_packet(), (3, 0));
+        assert_eq!(network.nat, Some((3, 49)));
+    }
+
+    #[test]
+    fn packets_are_relayed() {
+        let mut network = Network::new(&RELAY, 5);
+        network.round();
+        network.queues[1].push_back((2, 7));
+        assert!(network.round());
+        assert_eq!(network.nat, Some((3, 10)));
+        assert!(network.is_idle());
+        assert!(!network.round());
+    }
+
+    #[test]
+    fn nat_wakes_idle_network() {
+        let mut network = Network::new(&RELAY, NICS);
+        assert_eq!(network.repeated_wake_up(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown address")]
+    fn unknown_addresses_are_rejected() {
+        let mut network = Network::new(&RELAY, 2);
+        network.round();
+        network.queues[0].push_back((5, 0));
+        network.round();
+    }
+}
@@ -0,0 +1,263 @@
+use crate::year_2019::point::{Direction, Point};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+
+const SIZE: i64 = 5;
+const CENTER: usize = 12;
+
+/// One bit per tile, row by row from the top left, set where there is a bug.
+/// Read as a number it is the biodiversity rating.
+type Layout = u32;
+
+/// For every tile the adjacent tiles as the level they are on, relative to
+/// the tile's level, and their index. Deeper levels are inside the center
+/// tile.
+type Neighbours = Vec<Vec<(i64, usize)>>;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_24_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let layout = first_repeat(parse_layout(&input));
+    println!("Biodiversity of the first repeated layout: {}", layout);
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_24_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let bugs = recursive_bugs(parse_layout(&input), 200);
+    println!("Bugs after 200 minutes: {}", bugs);
+}
+
+fn parse_layout(input: &str) -> Layout {
+    input
+        .chars()
+        .filter(|c| *c == '#' || *c == '.')
+        .enumerate()
+        .filter(|&(_, c)| c == '#')
+        .fold(0, |layout, (i, _)| layout | 1 << i)
+}
+
+fn index(point: Point) -> usize {
+    (point.y * SIZE + point.x) as usize
+}
+
+/// The tiles next to the center tile see the whole facing edge of the grid
+/// one level down, and the tiles on the edge see the tile next to the center
+/// one level up. Without recursion the grid is flat and the center tile is
+/// an ordinary tile.
+fn neighbour_table(recursive: bool) -> Neighbours {
+    let center = Point::new(2, 2);
+    (0..SIZE * SIZE)
+        .map(|i| {
+            let tile = Point::new(i % SIZE, i / SIZE);
+            if recursive && tile == center {
+                return vec![];
+            }
+            let mut neighbours = vec![];
+            for direction in Direction::ALL.iter() {
+                let offset = direction.offset();
+                let next = tile + offset;
+                let outside = next.x < 0 || next.y < 0 || SIZE <= next.x || SIZE <= next.y;
+                if !recursive {
+                    if !outside {
+                        neighbours.push((0, index(next)));
+                    }
+                } else if outside {
+                    neighbours.push((-1, index(center + offset)));
+                } else if next == center {
+                    // The inner edge facing the tile, e.g. the left column
+                    // when the tile is left of the center.
+                    let edge = center - offset * 2;
+                    let along = Point::new(offset.y, offset.x);
+                    for k in -2..=2 {
+                        neighbours.push((1, index(edge + along * k)));
+                    }
+                } else {
+                    neighbours.push((0, index(next)));
+                }
+            }
+            neighbours
+        })
+        .collect()
+}
+
+/// A bug dies unless exactly one bug is adjacent, an empty tile becomes
+/// infested if one or two bugs are adjacent. Levels without bugs are left
+/// out, and the levels right outside and inside the occupied ones are
+/// included since bugs can spread into them.
+fn step(levels: &BTreeMap<i64, Layout>, table: &Neighbours) -> BTreeMap<i64, Layout> {
+    let (min, max) = match (levels.keys().next(), levels.keys().next_back()) {
+        (Some(&min), Some(&max)) => (min, max),
+        _ => return BTreeMap::new(),
+    };
+    let bug = |level: i64, tile: usize| levels.get(&level).map_or(0, |l| l >> tile & 1);
+    let mut next = BTreeMap::new();
+    for level in min - 1..=max + 1 {
+        let mut layout = 0;
+        for (tile, neighbours) in table.iter().enumerate() {
+            let adjacent: u32 = neighbours
+                .iter()
+                .map(|&(depth, other)| bug(level + depth, other))
+                .sum();
+            if matches!((bug(level, tile), adjacent), (1, 1) | (0, 1) | (0, 2)) {
+                layout |= 1 << tile;
+            }
+        }
+        if layout != 0 {
+            next.insert(level, layout);
+        }
+    }
+    next
+}
+
+/// The first layout that appears twice on a single flat grid.
+fn first_repeat(layout: Layout) -> Layout {
+    let table = neighbour_table(false);
+    let mut seen = HashSet::new();
+    let mut levels: BTreeMap<i64, Layout> = [(0, layout)].iter().copied().collect();
+    loop {
+        let layout = levels.get(&0).copied().unwrap_or(0);
+        if !seen.insert(layout) {
+            return layout;
+        }
+        levels = step(&levels, &table);
+    }
+}
+
+/// Bugs on all levels after `minutes` on recursive grids, starting with
+/// `layout` on level 0 and no bugs elsewhere.
+fn recursive_bugs(layout: Layout, minutes: usize) -> u32 {
+    let table = neighbour_table(true);
+    let levels = [(0, layout & !(1 << CENTER))].iter().copied().collect();
+    (0..minutes)
+        .fold(levels, |levels, _| step(&levels, &table))
+        .values()
+        .map(|layout| layout.count_ones())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "....#
+#..#.
+#..##
+..#..
+#....";
+
+    fn neighbours(table: &Neighbours, tile: usize) -> Vec<(i64, usize)> {
+        let mut neighbours = table[tile].clone();
+        neighbours.sort_unstable();
+        neighbours
+    }
+
+    #[test]
+    fn example_24_1() {
+        let layout = parse_layout(EXAMPLE);
+        let table = neighbour_table(false);
+        let after: BTreeMap<i64, Layout> = [(0, layout)].iter().copied().collect();
+        let after = step(&after, &table);
+        let expected = "#..#.
+####.
+###.#
+##.##
+.##..";
+        assert_eq!(after[&0], parse_layout(expected));
+        assert_eq!(first_repeat(layout), 2129920);
+    }
+
+    #[test]
+    fn example_24_2() {
+        assert_eq!(recursive_bugs(parse_layout(EXAMPLE), 10), 99);
+    }
+
+    #[test]
+    fn biodiversity_is_the_layout() {
+        let layout = parse_layout(
+            ".....
+.....
+.....
+#....
+.#...",
+        );
+        assert_eq!(layout, 2129920);
+    }
+
+    #[test]
+    fn flat_neighbours() {
+        let table = neighbour_table(false);
+        assert_eq!(neighbours(&table, 0), vec![(0, 1), (0, 5)]);
+        assert_eq!(
+            neighbours(&table, CENTER),
+            vec![(0, 7), (0, 11), (0, 13), (0, 17)]
+        );
+        assert_eq!(neighbours(&table, 24), vec![(0, 19), (0, 23)]);
+    }
+
+    #[test]
+    fn recursive_neighbours() {
+        let table = neighbour_table(true);
+        // Tile 19 in the puzzle description.
+        assert_eq!(
+            neighbours(&table, 18),
+            vec![(0, 13), (0, 17), (0, 19), (0, 23)]
+        );
+        // Tiles G, D and E.
+        assert_eq!(neighbours(&table, 6), vec![(0, 1), (0, 5), (0, 7), (0, 11)]);
+        assert_eq!(neighbours(&table, 3), vec![(-1, 7), (0, 2), (0, 4), (0, 8)]);
+        assert_eq!(
+            neighbours(&table, 4),
+            vec![(-1, 7), (-1, 13), (0, 3), (0, 9)]
+        );
+        // Tile 14 sees the right column of the level inside.
+        assert_eq!(
+            neighbours(&table, 13),
+            vec![
+                (0, 8),
+                (0, 14),
+                (0, 18),
+                (1, 4),
+                (1, 9),
+                (1, 14),
+                (1, 19),
+                (1, 24)
+            ]
+        );
+        // Tile H sees the top row of the level inside.
+        assert_eq!(
+            neighbours(&table, 7),
+            vec![
+                (0, 2),
+                (0, 6),
+                (0, 8),
+                (1, 0),
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (1, 4)
+            ]
+        );
+        assert!(table[CENTER].is_empty());
+    }
+
+    #[test]
+    fn recursive_neighbours_are_symmetric() {
+        let table = neighbour_table(true);
+        for (tile, neighbours) in table.iter().enumerate() {
+            let expected = if [7, 11, 13, 17].contains(&tile) {
+                8
+            } else {
+                4
+            };
+            if tile != CENTER {
+                assert_eq!(neighbours.len(), expected, "{}", tile);
+            }
+            for &(depth, other) in neighbours {
+                assert!(table[other].contains(&(-depth, tile)), "{} {}", tile, other);
+            }
+        }
+    }
+}
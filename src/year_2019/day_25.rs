@@ -0,0 +1,395 @@
+use crate::year_2019::intcode::{ascii, parse_program, Intcode};
+use crate::year_2019::point::Direction;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+
+/// Items that end the game or get the droid stuck when taken. The room text
+/// does not tell them apart from the others.
+const TRAPS: [&str; 5] = [
+    "escape pod",
+    "giant electromagnet",
+    "infinite loop",
+    "molten lava",
+    "photons",
+];
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_25_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let (mut droid, intro) = Droid::new(&parse_program(&input));
+    let text = solve(&intro, |command| droid.send(command)).expect("Checkpoint not passed");
+    print!("{}", text);
+    match password(&text) {
+        Some(password) => println!("Password: {}", password),
+        None => println!("No password found"),
+    }
+}
+
+/// Plays the adventure with commands typed on stdin, until the program halts
+/// or stdin ends.
+#[allow(dead_code)]
+pub fn play() {
+    let location = "input/year_2019/day_25_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let (mut droid, intro) = Droid::new(&parse_program(&input));
+    print!("{}", intro);
+    while !droid.intcode.is_halted() {
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        print!("{}", droid.send(line.trim()));
+    }
+}
+
+/// The droid behind its ASCII interface. Every command is sent as a line and
+/// answered with the text printed until the droid asks for the next one.
+struct Droid {
+    intcode: Intcode,
+}
+
+impl Droid {
+    /// Boots the droid and returns the text it prints before the first
+    /// command.
+    fn new(program: &[i64]) -> (Self, String) {
+        let mut intcode = Intcode::new(program.to_vec()).expect("Program cannot be empty");
+        intcode.reset().run().expect("Droid program failed");
+        let (intro, _) = ascii::take_text(&mut intcode);
+        (Droid { intcode }, intro)
+    }
+
+    fn send(&mut self, command: &str) -> String {
+        ascii::push_line(&mut self.intcode, command);
+        self.intcode.run().expect("Droid program failed");
+        ascii::take_text(&mut self.intcode).0
+    }
+}
+
+fn command(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Up => "north",
+        Direction::Right => "east",
+        Direction::Down => "south",
+        Direction::Left => "west",
+    }
+}
+
+fn parse_direction(input: &str) -> Option<Direction> {
+    match input {
+        "north" => Some(Direction::Up),
+        "east" => Some(Direction::Right),
+        "south" => Some(Direction::Down),
+        "west" => Some(Direction::Left),
+        _ => None,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Room {
+    name: String,
+    doors: Vec<Direction>,
+    items: Vec<String>,
+}
+
+/// The last room described in `text`. Being thrown out of a room describes
+/// two rooms in a row, and the droid ends up in the second one.
+fn parse_room(text: &str) -> Option<Room> {
+    let start = text.rfind("== ")?;
+    let mut lines = text[start..].lines();
+    let name = lines.next()?.trim_matches(|c| c == '=' || c == ' ');
+    let mut room = Room {
+        name: name.to_string(),
+        doors: vec![],
+        items: vec![],
+    };
+    let mut list = None;
+    for line in lines {
+        match line {
+            "Doors here lead:" => list = Some(true),
+            "Items here:" => list = Some(false),
+            _ => match (line.strip_prefix("- "), list) {
+                (Some(door), Some(true)) => room.doors.extend(parse_direction(door)),
+                (Some(item), Some(false)) => room.items.push(item.to_string()),
+                _ => list = None,
+            },
+        }
+    }
+    Some(room)
+}
+
+/// The numbers after "typing" in the message that lets the droid in.
+fn password(text: &str) -> Option<String> {
+    let start = text.find("typing ")? + "typing ".len();
+    let password: String = text[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    if password.is_empty() {
+        None
+    } else {
+        Some(password)
+    }
+}
+
+/// The way from the start to the security checkpoint and on to the
+/// pressure-sensitive floor.
+struct Checkpoint {
+    name: String,
+    path: Vec<Direction>,
+    floor: Direction,
+}
+
+struct Explorer<F> {
+    send: F,
+    inventory: Vec<String>,
+    seen: HashSet<String>,
+    checkpoint: Option<Checkpoint>,
+}
+
+impl<F: FnMut(&str) -> String> Explorer<F> {
+    /// Maps the ship depth first, picking up every item that is not a known
+    /// trap, and walks back through each door after exploring behind it.
+    /// A door that throws the droid back leads to the pressure-sensitive
+    /// floor and is only noted.
+    fn visit(&mut self, room: &Room, path: &mut Vec<Direction>) {
+        self.seen.insert(room.name.clone());
+        for item in &room.items {
+            if !TRAPS.contains(&item.as_str()) {
+                (self.send)(&format!("take {}", item));
+                self.inventory.push(item.clone());
+            }
+        }
+        for &door in &room.doors {
+            if path.last() == Some(&door.reverse()) {
+                continue;
+            }
+            let next = parse_room(&(self.send)(command(door))).expect("No room after moving");
+            if next.name == room.name {
+                self.checkpoint = Some(Checkpoint {
+                    name: room.name.clone(),
+                    path: path.clone(),
+                    floor: door,
+                });
+                continue;
+            }
+            if !self.seen.contains(&next.name) {
+                path.push(door);
+                self.visit(&next, path);
+                path.pop();
+            }
+            (self.send)(command(door.reverse()));
+        }
+    }
+
+    /// Walks to the checkpoint and steps onto the floor with every subset of
+    /// the inventory, changing one item at a time in Gray code order. Returns
+    /// the text printed once the droid is let through.
+    fn pass_checkpoint(&mut self) -> Option<String> {
+        let checkpoint = self.checkpoint.as_ref()?;
+        for &direction in &checkpoint.path {
+            (self.send)(command(direction));
+        }
+        for i in 0..1u32 << self.inventory.len() {
+            if 0 < i {
+                let item = i.trailing_zeros();
+                let dropped = (i ^ i >> 1) & 1 << item != 0;
+                let action = if dropped { "drop" } else { "take" };
+                (self.send)(&format!("{} {}", action, self.inventory[item as usize]));
+            }
+            let text = (self.send)(command(checkpoint.floor));
+            if parse_room(&text).is_none_or(|room| room.name != checkpoint.name) {
+                return Some(text);
+            }
+        }
+        None
+    }
+}
+
+/// Explores the ship starting from the room described in `intro` and gets
+/// through the security checkpoint. Returns the text printed at the end.
+fn solve<F: FnMut(&str) -> String>(intro: &str, send: F) -> Option<String> {
+    let start = parse_room(intro)?;
+    let mut explorer = Explorer {
+        send,
+        inventory: vec![],
+        seen: HashSet::new(),
+        checkpoint: None,
+    };
+    explorer.visit(&start, &mut vec![]);
+    explorer.pass_checkpoint()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Each door and the room it leads to.
+    type Doors = Vec<(&'static str, &'static str)>;
+
+    /// Rooms with their doors and items, as the adventure prints them.
+    struct Ship {
+        rooms: HashMap<&'static str, (Doors, Vec<&'static str>)>,
+        position: &'static str,
+        inventory: HashSet<String>,
+        required: Vec<&'static str>,
+    }
+
+    impl Ship {
+        fn describe(&self, name: &str) -> String {
+            let (doors, items) = &self.rooms[name];
+            let mut text = format!("\n\n\n== {} ==\nA room.\n\nDoors here lead:\n", name);
+            for (door, _) in doors {
+                text += &format!("- {}\n", door);
+            }
+            if !items.is_empty() {
+                text += "\nItems here:\n";
+                for item in items {
+                    text += &format!("- {}\n", item);
+                }
+            }
+            text + "\nCommand?\n"
+        }
+
+        fn send(&mut self, command: &str) -> String {
+            if let Some(item) = command.strip_prefix("take ") {
+                assert!(!TRAPS.contains(&item), "Took {}", item);
+                let items = &mut self.rooms.get_mut(self.position).unwrap().1;
+                items.retain(|i| *i != item);
+                self.inventory.insert(item.to_string());
+                return format!("\nYou take the {}.\n\nCommand?\n", item);
+            }
+            if let Some(item) = command.strip_prefix("drop ") {
+                assert!(self.inventory.remove(item));
+                return format!("\nYou drop the {}.\n\nCommand?\n", item);
+            }
+            let (doors, _) = &self.rooms[self.position];
+            let next = doors
+                .iter()
+                .find(|(door, _)| *door == command)
+                .map(|&(_, next)| next)
+                .unwrap();
+            if next != "Pressure-Sensitive Floor" {
+                self.position = next;
+                return self.describe(next);
+            }
+            let mut required: Vec<String> = self.required.iter().map(|i| i.to_string()).collect();
+            let mut held: Vec<String> = self.inventory.iter().cloned().collect();
+            required.sort();
+            held.sort();
+            if held == required {
+                String::from(
+                    "\n\n\n== Pressure-Sensitive Floor ==\nAnalyzing...\n\nDoors here lead:\n- \
+                     west\n\nA loud, robotic voice says \"Analysis complete! You may proceed.\" \
+                     \"Oh, hello! You should be able to get in by typing 2424308736 on the \
+                     keypad at the main airlock.\"\n",
+                )
+            } else {
+                String::from(
+                    "\n\n\n== Pressure-Sensitive Floor ==\nAnalyzing...\n\nDoors here lead:\n- \
+                     west\n\nA loud, robotic voice says \"Alert! Droids on this ship are \
+                     lighter than the detected value!\" and you are ejected back to the \
+                     checkpoint.\n",
+                ) + &self.describe(self.position)
+            }
+        }
+    }
+
+    fn ship() -> Ship {
+        let mut rooms = HashMap::new();
+        rooms.insert(
+            "Hull Breach",
+            (vec![("north", "Kitchen"), ("east", "Hallway")], vec!["mug"]),
+        );
+        rooms.insert(
+            "Kitchen",
+            (vec![("south", "Hull Breach")], vec!["infinite loop", "pan"]),
+        );
+        rooms.insert(
+            "Hallway",
+            (
+                vec![("west", "Hull Breach"), ("south", "Storage")],
+                vec!["coin"],
+            ),
+        );
+        rooms.insert(
+            "Storage",
+            (
+                vec![("north", "Hallway"), ("south", "Security Checkpoint")],
+                vec!["photons", "shell"],
+            ),
+        );
+        rooms.insert(
+            "Security Checkpoint",
+            (
+                vec![("north", "Storage"), ("east", "Pressure-Sensitive Floor")],
+                vec![],
+            ),
+        );
+        Ship {
+            rooms,
+            position: "Hull Breach",
+            inventory: HashSet::new(),
+            required: vec!["coin", "pan"],
+        }
+    }
+
+    #[test]
+    fn rooms_are_parsed() {
+        let ship = ship();
+        let room = parse_room(&ship.describe("Storage")).unwrap();
+        assert_eq!(
+            room,
+            Room {
+                name: String::from("Storage"),
+                doors: vec![Direction::Up, Direction::Down],
+                items: vec![String::from("photons"), String::from("shell")],
+            }
+        );
+        let room = parse_room(&ship.describe("Security Checkpoint")).unwrap();
+        assert!(room.items.is_empty());
+        assert_eq!(parse_room("\nYou take the mug.\n\nCommand?\n"), None);
+    }
+
+    #[test]
+    fn ejection_ends_in_checkpoint() {
+        let mut ship = ship();
+        ship.position = "Security Checkpoint";
+        let room = parse_room(&ship.send("east")).unwrap();
+        assert_eq!(room.name, "Security Checkpoint");
+        assert_eq!(room.doors, vec![Direction::Up, Direction::Right]);
+    }
+
+    #[test]
+    fn explorer_passes_checkpoint() {
+        let mut ship = ship();
+        let intro = ship.describe("Hull Breach");
+        let text = solve(&intro, |command| ship.send(command)).unwrap();
+        assert_eq!(password(&text), Some(String::from("2424308736")));
+        let mut held: Vec<&String> = ship.inventory.iter().collect();
+        held.sort();
+        assert_eq!(held, vec!["coin", "pan"]);
+        assert_eq!(ship.position, "Security Checkpoint");
+        assert_eq!(ship.rooms["Kitchen"].1, vec!["infinite loop"]);
+        assert_eq!(ship.rooms["Storage"].1, vec!["photons"]);
+    }
+
+    #[test]
+    fn impossible_checkpoint() {
+        let mut ship = ship();
+        ship.required = vec!["mug", "photons"];
+        let intro = ship.describe("Hull Breach");
+        assert_eq!(solve(&intro, |command| ship.send(command)), None);
+    }
+
+    #[test]
+    fn droid_speaks_ascii() {
+        // Prints "?" and then echoes every line it reads.
+        let program = vec![104, 63, 104, 10, 3, 100, 4, 100, 1105, 1, 4];
+        let (mut droid, intro) = Droid::new(&program);
+        assert_eq!(intro, "?\n");
+        assert_eq!(droid.send("north"), "north\n");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::year_2019::point::{Direction, Point};
 use core::cmp;
 use std::fs;
 
@@ -24,10 +25,10 @@ pub fn part_2() {
 }
 
 fn parse_points(input: &str) -> Vec<Point> {
-    let mut points: Vec<Point> = vec![Point { x: 0, y: 0 }];
-    let mut current = Point { x: 0, y: 0 };
-    input.split(',').map(Direction::from).for_each(|d| {
-        current = Point::new(&current, &d);
+    let mut points: Vec<Point> = vec![Point::new(0, 0)];
+    let mut current = Point::new(0, 0);
+    input.split(',').map(Segment::from).for_each(|s| {
+        current = current + s.direction.offset() * s.length;
         points.push(current);
     });
     points
@@ -62,7 +63,7 @@ fn find_intersections(points1: &[Point], points2: &[Point]) -> Vec<Intersection>
 }
 
 fn steps(point1: &Point, point2: &Point) -> u64 {
-    ((point2.x - point1.x).abs() + (point2.y - point1.y).abs()) as u64
+    (*point2 - *point1).manhattan() as u64
 }
 
 fn min_manhattan_distance(wire1: &[Point], wire2: &[Point]) -> u64 {
@@ -70,7 +71,7 @@ fn min_manhattan_distance(wire1: &[Point], wire2: &[Point]) -> u64 {
     intersections
         .iter()
         .filter(|i| i.point.x != 0 && i.point.y != 0)
-        .map(|i| (i.point.x.abs() + i.point.y.abs()) as u64)
+        .map(|i| i.point.manhattan() as u64)
         .min()
         .unwrap_or(0)
 }
@@ -85,57 +86,27 @@ fn min_steps(wire1: &[Point], wire2: &[Point]) -> u64 {
         .unwrap_or(0)
 }
 
-enum Direction {
-    Up(i64),
-    Right(i64),
-    Down(i64),
-    Left(i64),
+/// One straight piece of a wire.
+struct Segment {
+    direction: Direction,
+    length: i64,
 }
 
-impl From<&str> for Direction {
+impl From<&str> for Segment {
     fn from(item: &str) -> Self {
         let mut chars = item.chars();
-        let direction: char = chars.next().expect("Cannot parse direction");
-        let distance = chars
+        let direction = match chars.next().expect("Cannot parse direction") {
+            'U' => Direction::Up,
+            'R' => Direction::Right,
+            'D' => Direction::Down,
+            'L' => Direction::Left,
+            _ => panic!("Cannot parse direction"),
+        };
+        let length = chars
             .collect::<String>()
             .parse::<i64>()
             .expect("Cannot parse distance");
-        match direction {
-            'U' => Direction::Up(distance),
-            'R' => Direction::Right(distance),
-            'D' => Direction::Down(distance),
-            'L' => Direction::Left(distance),
-            _ => panic!("Cannot parse direction"),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-struct Point {
-    x: i64,
-    y: i64,
-}
-
-impl Point {
-    fn new(point: &Point, dir: &Direction) -> Self {
-        match dir {
-            Direction::Up(distance) => Point {
-                x: point.x,
-                y: point.y + distance,
-            },
-            Direction::Right(distance) => Point {
-                x: point.x + distance,
-                y: point.y,
-            },
-            Direction::Down(distance) => Point {
-                x: point.x,
-                y: point.y - distance,
-            },
-            Direction::Left(distance) => Point {
-                x: point.x - distance,
-                y: point.y,
-            },
-        }
+        Segment { direction, length }
     }
 }
 
@@ -146,10 +117,7 @@ fn intersect(line1: &[Point], line2: &[Point]) -> Option<Point> {
         let bottom = cmp::min(line2[0].y, line2[1].y);
         let top = cmp::max(line2[0].y, line2[1].y);
         if (left..=right).contains(&line2[0].x) && (bottom..=top).contains(&line1[0].y) {
-            Some(Point {
-                x: line2[0].x,
-                y: line1[0].y,
-            })
+            Some(Point::new(line2[0].x, line1[0].y))
         } else {
             None
         }
@@ -159,10 +127,7 @@ fn intersect(line1: &[Point], line2: &[Point]) -> Option<Point> {
         let bottom = cmp::min(line1[0].y, line1[1].y);
         let top = cmp::max(line1[0].y, line1[1].y);
         if (left..=right).contains(&line1[0].x) && (bottom..=top).contains(&line2[0].y) {
-            Some(Point {
-                x: line1[0].x,
-                y: line2[0].y,
-            })
+            Some(Point::new(line1[0].x, line2[0].y))
         } else {
             None
         }
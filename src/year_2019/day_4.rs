@@ -75,6 +75,7 @@ fn get_digits(num: u64) -> Vec<u8> {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
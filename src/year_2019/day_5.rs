@@ -10,7 +10,9 @@ pub fn part_1() {
         .filter_map(|s| s.parse::<i64>().ok())
         .collect::<Vec<i64>>();
     let mut intcode = Intcode::new(input).expect("Input cannot be intialized");
-    intcode.reset().run();
+    intcode.reset().push_input(1).run().expect("Program failed");
+    let outputs = intcode.take_outputs();
+    println!("Test outputs: {:?}", outputs);
     // 6731945
 }
 
@@ -23,7 +25,7 @@ mod tests {
         let input = vec![1002, 4, 3, 4, 33];
         let output = vec![1002, 4, 3, 4, 99];
         let mut intcode = Intcode::new(input).unwrap();
-        intcode.reset().run();
+        intcode.reset().run().unwrap();
         assert_eq!(intcode.dump(), output);
     }
 
@@ -32,7 +34,67 @@ mod tests {
         let input = vec![1101, 100, -1, 4, 0];
         let output = vec![1101, 100, -1, 4, 99];
         let mut intcode = Intcode::new(input).unwrap();
-        intcode.reset().run();
+        intcode.reset().run().unwrap();
         assert_eq!(intcode.dump(), output);
     }
+
+    fn run_with_input(program: &[i64], input: i64) -> Vec<i64> {
+        let mut intcode = Intcode::new(program.to_vec()).unwrap();
+        intcode.reset().push_input(input).run().unwrap();
+        intcode.take_outputs()
+    }
+
+    #[test]
+    fn example_5_3() {
+        let program = vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+        assert_eq!(run_with_input(&program, 8), vec![1]);
+        assert_eq!(run_with_input(&program, 7), vec![0]);
+    }
+
+    #[test]
+    fn example_5_4() {
+        let program = vec![3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8];
+        assert_eq!(run_with_input(&program, 7), vec![1]);
+        assert_eq!(run_with_input(&program, 8), vec![0]);
+    }
+
+    #[test]
+    fn example_5_5() {
+        let program = vec![3, 3, 1108, -1, 8, 3, 4, 3, 99];
+        assert_eq!(run_with_input(&program, 8), vec![1]);
+        assert_eq!(run_with_input(&program, 9), vec![0]);
+    }
+
+    #[test]
+    fn example_5_6() {
+        let program = vec![3, 3, 1107, -1, 8, 3, 4, 3, 99];
+        assert_eq!(run_with_input(&program, -3), vec![1]);
+        assert_eq!(run_with_input(&program, 8), vec![0]);
+    }
+
+    #[test]
+    fn example_5_7() {
+        let program = vec![3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9];
+        assert_eq!(run_with_input(&program, 0), vec![0]);
+        assert_eq!(run_with_input(&program, 5), vec![1]);
+    }
+
+    #[test]
+    fn example_5_8() {
+        let program = vec![3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+        assert_eq!(run_with_input(&program, 0), vec![0]);
+        assert_eq!(run_with_input(&program, 5), vec![1]);
+    }
+
+    #[test]
+    fn example_5_9() {
+        let program = vec![
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        assert_eq!(run_with_input(&program, 7), vec![999]);
+        assert_eq!(run_with_input(&program, 8), vec![1000]);
+        assert_eq!(run_with_input(&program, 9), vec![1001]);
+    }
 }
@@ -0,0 +1,192 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_6_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let map = parse_orbits(&input).expect("Invalid orbit map");
+    println!("Direct and indirect orbits: {}", map.count_orbits());
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_6_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let map = parse_orbits(&input).expect("Invalid orbit map");
+    let transfers = map
+        .transfers("YOU", "SAN")
+        .expect("YOU and SAN must orbit something");
+    println!("Orbital transfers: {}", transfers);
+}
+
+#[derive(Debug, PartialEq)]
+enum OrbitError {
+    Malformed(String),
+    MultipleParents(String),
+    Cycle(String),
+}
+
+/// Every object mapped to the object it directly orbits, together with its
+/// depth, the number of objects it orbits directly and indirectly.
+struct OrbitMap {
+    parents: HashMap<String, String>,
+    depths: HashMap<String, usize>,
+}
+
+fn parse_orbits(input: &str) -> Result<OrbitMap, OrbitError> {
+    let mut parents: HashMap<String, String> = HashMap::new();
+    for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let (center, satellite) = match line.split(')').collect::<Vec<&str>>()[..] {
+            [center, satellite] if !center.is_empty() && !satellite.is_empty() => {
+                (center, satellite)
+            }
+            _ => return Err(OrbitError::Malformed(line.to_string())),
+        };
+        if let Some(parent) = parents.get(satellite) {
+            if parent != center {
+                return Err(OrbitError::MultipleParents(satellite.to_string()));
+            }
+        }
+        parents.insert(satellite.to_string(), center.to_string());
+    }
+    let depths = find_depths(&parents)?;
+    Ok(OrbitMap { parents, depths })
+}
+
+/// Walks from every object towards the root, reusing the depths that are
+/// already known, and reports an object that is reached twice on one walk.
+fn find_depths(parents: &HashMap<String, String>) -> Result<HashMap<String, usize>, OrbitError> {
+    let mut depths: HashMap<String, usize> = HashMap::new();
+    for object in parents.keys() {
+        let mut path: Vec<&str> = vec![];
+        let mut on_path: HashSet<&str> = HashSet::new();
+        let mut current = object.as_str();
+        let mut depth = loop {
+            if let Some(&depth) = depths.get(current) {
+                break depth;
+            }
+            if !on_path.insert(current) {
+                return Err(OrbitError::Cycle(current.to_string()));
+            }
+            match parents.get(current) {
+                Some(parent) => {
+                    path.push(current);
+                    current = parent;
+                }
+                None => {
+                    depths.insert(current.to_string(), 0);
+                    break 0;
+                }
+            }
+        };
+        for object in path.into_iter().rev() {
+            depth += 1;
+            depths.insert(object.to_string(), depth);
+        }
+    }
+    Ok(depths)
+}
+
+impl OrbitMap {
+    fn count_orbits(&self) -> usize {
+        self.depths.values().sum()
+    }
+
+    fn ancestors<'a>(&'a self, object: &'a str) -> Vec<&'a str> {
+        let mut ancestors = vec![];
+        let mut current = object;
+        while let Some(parent) = self.parents.get(current) {
+            ancestors.push(parent.as_str());
+            current = parent;
+        }
+        ancestors
+    }
+
+    /// Number of transfers needed to move from the object `from` orbits to
+    /// the object `to` orbits, going through their lowest common ancestor.
+    fn transfers(&self, from: &str, to: &str) -> Option<usize> {
+        let start = self.parents.get(from)?;
+        let end = self.parents.get(to)?;
+        let end_ancestors: HashSet<&str> = self.ancestors(end).into_iter().collect();
+        let common = std::iter::once(start.as_str())
+            .chain(self.ancestors(start))
+            .find(|a| *a == end || end_ancestors.contains(a))?;
+        let depth = |object: &str| self.depths.get(object).copied();
+        Some(depth(start)? + depth(end)? - 2 * depth(common)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L";
+    const EXAMPLE_2: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN";
+
+    #[test]
+    fn example_6_1() {
+        let map = parse_orbits(EXAMPLE_1).unwrap();
+        assert_eq!(map.count_orbits(), 42);
+    }
+
+    #[test]
+    fn example_6_2() {
+        let map = parse_orbits(EXAMPLE_1).unwrap();
+        assert_eq!(map.depths["D"], 3);
+        assert_eq!(map.depths["L"], 7);
+        assert_eq!(map.depths["COM"], 0);
+    }
+
+    #[test]
+    fn example_6_3() {
+        let map = parse_orbits(EXAMPLE_2).unwrap();
+        assert_eq!(map.transfers("YOU", "SAN"), Some(4));
+    }
+
+    #[test]
+    fn unordered_input() {
+        let map = parse_orbits("C)D\nB)C\nCOM)B").unwrap();
+        assert_eq!(map.count_orbits(), 6);
+    }
+
+    #[test]
+    fn transfers_along_one_branch() {
+        let map = parse_orbits("COM)A\nA)B\nB)C\nC)YOU\nA)SAN").unwrap();
+        assert_eq!(map.transfers("YOU", "SAN"), Some(2));
+        assert_eq!(map.transfers("SAN", "YOU"), Some(2));
+        assert_eq!(map.transfers("YOU", "COM"), None);
+    }
+
+    #[test]
+    fn transfers_between_separate_trees() {
+        let map = parse_orbits("COM)YOU\nX)SAN").unwrap();
+        assert_eq!(map.transfers("YOU", "SAN"), None);
+    }
+
+    #[test]
+    fn multiple_parents_are_rejected() {
+        let result = parse_orbits("COM)B\nCOM)C\nB)D\nC)D");
+        assert_eq!(
+            result.err(),
+            Some(OrbitError::MultipleParents(String::from("D")))
+        );
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let result = parse_orbits("COM)B\nA)C\nC)D\nD)A");
+        assert!(matches!(result.err(), Some(OrbitError::Cycle(_))));
+        let result = parse_orbits("A)A");
+        assert_eq!(result.err(), Some(OrbitError::Cycle(String::from("A"))));
+    }
+
+    #[test]
+    fn malformed_lines_are_rejected() {
+        let result = parse_orbits("COM)B\nB-C");
+        assert_eq!(
+            result.err(),
+            Some(OrbitError::Malformed(String::from("B-C")))
+        );
+    }
+}
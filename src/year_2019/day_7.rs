@@ -0,0 +1,164 @@
+use crate::year_2019::intcode::{parse_program, Intcode};
+use std::fs;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_7_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let program = parse_program(&input);
+    let (signal, phases) = max_thruster_signal(&program, &[0, 1, 2, 3, 4], run_series);
+    println!("Highest signal: {} with phases {:?}", signal, phases);
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_7_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let program = parse_program(&input);
+    let (signal, phases) = max_thruster_signal(&program, &[5, 6, 7, 8, 9], run_feedback);
+    println!("Highest signal: {} with phases {:?}", signal, phases);
+}
+
+/// Tries every ordering of `phases` and returns the highest signal `run`
+/// produces, together with the ordering that produced it.
+fn max_thruster_signal(
+    program: &[i64],
+    phases: &[i64],
+    run: fn(&[i64], &[i64]) -> i64,
+) -> (i64, Vec<i64>) {
+    permutations(phases)
+        .into_iter()
+        .map(|p| (run(program, &p), p))
+        .max_by_key(|(signal, _)| *signal)
+        .expect("There is at least one permutation")
+}
+
+fn permutations(values: &[i64]) -> Vec<Vec<i64>> {
+    if values.len() <= 1 {
+        return vec![values.to_vec()];
+    }
+    let mut result = vec![];
+    for (i, &first) in values.iter().enumerate() {
+        let mut rest = values.to_vec();
+        rest.remove(i);
+        for mut permutation in permutations(&rest) {
+            permutation.insert(0, first);
+            result.push(permutation);
+        }
+    }
+    result
+}
+
+fn create_amplifiers(program: &[i64], phases: &[i64]) -> Vec<Intcode> {
+    phases
+        .iter()
+        .map(|&phase| {
+            let mut amplifier = Intcode::new(program.to_vec()).expect("Program cannot be empty");
+            amplifier.reset().push_input(phase);
+            amplifier
+        })
+        .collect()
+}
+
+/// Passes the signal through each amplifier once.
+fn run_series(program: &[i64], phases: &[i64]) -> i64 {
+    create_amplifiers(program, phases)
+        .iter_mut()
+        .fold(0, |signal, amplifier| {
+            amplifier
+                .push_input(signal)
+                .run()
+                .expect("Amplifier program failed");
+            amplifier.take_output().expect("Amplifier has no output")
+        })
+}
+
+/// Feeds the output of the last amplifier back into the first one until the
+/// last amplifier halts, pausing each amplifier while it waits for input.
+fn run_feedback(program: &[i64], phases: &[i64]) -> i64 {
+    let mut amplifiers = create_amplifiers(program, phases);
+    let mut signal = 0;
+    loop {
+        for amplifier in amplifiers.iter_mut() {
+            amplifier
+                .push_input(signal)
+                .run()
+                .expect("Amplifier program failed");
+            if let Some(&output) = amplifier.take_outputs().last() {
+                signal = output;
+            }
+        }
+        if amplifiers.iter().all(|a| a.is_halted()) {
+            return signal;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_permutations() {
+        let permutations = permutations(&[0, 1, 2, 3, 4]);
+        assert_eq!(permutations.len(), 120);
+        assert!(permutations.contains(&vec![4, 3, 2, 1, 0]));
+    }
+
+    #[test]
+    fn example_7_1() {
+        let program = vec![
+            3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+        ];
+        assert_eq!(run_series(&program, &[4, 3, 2, 1, 0]), 43210);
+        let (signal, phases) = max_thruster_signal(&program, &[0, 1, 2, 3, 4], run_series);
+        assert_eq!(signal, 43210);
+        assert_eq!(phases, vec![4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn example_7_2() {
+        let program = vec![
+            3, 23, 3, 24, 1002, 24, 10, 24, 1002, 23, -1, 23, 101, 5, 23, 23, 1, 24, 23, 23, 4, 23,
+            99, 0, 0,
+        ];
+        let (signal, phases) = max_thruster_signal(&program, &[0, 1, 2, 3, 4], run_series);
+        assert_eq!(signal, 54321);
+        assert_eq!(phases, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn example_7_3() {
+        let program = vec![
+            3, 31, 3, 32, 1002, 32, 10, 32, 1001, 31, -2, 31, 1007, 31, 0, 33, 1002, 33, 7, 33, 1,
+            33, 31, 31, 1, 32, 31, 31, 4, 31, 99, 0, 0, 0,
+        ];
+        let (signal, phases) = max_thruster_signal(&program, &[0, 1, 2, 3, 4], run_series);
+        assert_eq!(signal, 65210);
+        assert_eq!(phases, vec![1, 0, 4, 3, 2]);
+    }
+
+    #[test]
+    fn example_7_4() {
+        let program = vec![
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        assert_eq!(run_feedback(&program, &[9, 8, 7, 6, 5]), 139629729);
+        let (signal, phases) = max_thruster_signal(&program, &[5, 6, 7, 8, 9], run_feedback);
+        assert_eq!(signal, 139629729);
+        assert_eq!(phases, vec![9, 8, 7, 6, 5]);
+    }
+
+    #[test]
+    fn example_7_5() {
+        let program = vec![
+            3, 52, 1001, 52, -5, 52, 3, 53, 1, 52, 56, 54, 1007, 54, 5, 55, 1005, 55, 26, 1001, 54,
+            -5, 54, 1105, 1, 12, 1, 53, 54, 53, 1008, 54, 0, 55, 1001, 55, 1, 55, 2, 53, 55, 53, 4,
+            53, 1001, 56, -1, 56, 1005, 56, 6, 99, 0, 0, 0, 0, 10,
+        ];
+        let (signal, phases) = max_thruster_signal(&program, &[5, 6, 7, 8, 9], run_feedback);
+        assert_eq!(signal, 18216);
+        assert_eq!(phases, vec![9, 7, 8, 5, 6]);
+    }
+}
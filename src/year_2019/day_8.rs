@@ -0,0 +1,179 @@
+use crate::year_2019::ocr;
+use std::env;
+use std::fs;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
+const BLACK: u8 = 0;
+const WHITE: u8 = 1;
+const TRANSPARENT: u8 = 2;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_8_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let layers = parse_layers(&input, WIDTH, HEIGHT);
+    println!("Layer checksum: {}", checksum(&layers));
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_8_1.txt";
+    let input: String = fs::read_to_string(location).expect("Cannot read input file");
+    let layers = parse_layers(&input, WIDTH, HEIGHT);
+    let image = decode(&layers);
+    print!("{}", render(&image, WIDTH));
+    let directory = env::temp_dir();
+    fs::write(directory.join("day_8.pbm"), to_pbm(&image, WIDTH)).expect("Cannot write PBM file");
+    fs::write(directory.join("day_8.pgm"), to_pgm(&image, WIDTH)).expect("Cannot write PGM file");
+    println!("Images written to {}", directory.display());
+    let message = ocr::read_letters(&to_pixels(&image, WIDTH)).expect("Cannot read message");
+    println!("Message: {}", message);
+}
+
+fn parse_layers(input: &str, width: usize, height: usize) -> Vec<Vec<u8>> {
+    let digits: Vec<u8> = input
+        .trim()
+        .chars()
+        .map(|c| c.to_digit(10).expect("Cannot parse pixel") as u8)
+        .collect();
+    digits
+        .chunks_exact(width * height)
+        .map(|layer| layer.to_vec())
+        .collect()
+}
+
+fn count_digit(layer: &[u8], digit: u8) -> usize {
+    layer.iter().filter(|&&d| d == digit).count()
+}
+
+/// Number of 1 digits multiplied by the number of 2 digits on the layer with
+/// the fewest 0 digits.
+fn checksum(layers: &[Vec<u8>]) -> usize {
+    let layer = layers
+        .iter()
+        .min_by_key(|layer| count_digit(layer, 0))
+        .expect("Image has no layers");
+    count_digit(layer, 1) * count_digit(layer, 2)
+}
+
+/// Stacks the layers with the first layer in front. A pixel shows the first
+/// colour that is not transparent.
+fn decode(layers: &[Vec<u8>]) -> Vec<u8> {
+    let size = layers.first().map_or(0, |layer| layer.len());
+    (0..size)
+        .map(|i| {
+            layers
+                .iter()
+                .map(|layer| layer[i])
+                .find(|&pixel| pixel != TRANSPARENT)
+                .unwrap_or(TRANSPARENT)
+        })
+        .collect()
+}
+
+fn render(image: &[u8], width: usize) -> String {
+    image
+        .chunks(width)
+        .map(|row| {
+            row.iter()
+                .map(|&pixel| if pixel == WHITE { '█' } else { ' ' })
+                .collect::<String>()
+                + "\n"
+        })
+        .collect()
+}
+
+fn to_pixels(image: &[u8], width: usize) -> Vec<Vec<bool>> {
+    image
+        .chunks(width)
+        .map(|row| row.iter().map(|&pixel| pixel == WHITE).collect())
+        .collect()
+}
+
+/// Plain PBM bitmap with the white message pixels drawn in black ink.
+fn to_pbm(image: &[u8], width: usize) -> String {
+    let height = image.len() / width;
+    let rows: Vec<String> = image
+        .chunks(width)
+        .map(|row| {
+            row.iter()
+                .map(|&pixel| if pixel == WHITE { "1" } else { "0" })
+                .collect::<Vec<&str>>()
+                .join(" ")
+        })
+        .collect();
+    format!("P1\n{} {}\n{}\n", width, height, rows.join("\n"))
+}
+
+/// Plain PGM greymap that keeps transparent pixels as mid grey.
+fn to_pgm(image: &[u8], width: usize) -> String {
+    let height = image.len() / width;
+    let rows: Vec<String> = image
+        .chunks(width)
+        .map(|row| {
+            row.iter()
+                .map(|&pixel| match pixel {
+                    BLACK => "0",
+                    WHITE => "255",
+                    _ => "128",
+                })
+                .collect::<Vec<&str>>()
+                .join(" ")
+        })
+        .collect();
+    format!("P2\n{} {}\n255\n{}\n", width, height, rows.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_8_1() {
+        let layers = parse_layers("123456789012", 3, 2);
+        assert_eq!(layers, vec![vec![1, 2, 3, 4, 5, 6], vec![7, 8, 9, 0, 1, 2]]);
+        assert_eq!(checksum(&layers), 1);
+    }
+
+    #[test]
+    fn example_8_2() {
+        let layers = parse_layers("0222112222120000", 2, 2);
+        let image = decode(&layers);
+        assert_eq!(image, vec![0, 1, 1, 0]);
+        assert_eq!(render(&image, 2), " █\n█ \n");
+    }
+
+    #[test]
+    fn checksum_uses_layer_with_fewest_zeros() {
+        let layers = parse_layers("001122111222000000", 3, 2);
+        assert_eq!(checksum(&layers), 9);
+    }
+
+    #[test]
+    fn pbm_and_pgm_images() {
+        let image = vec![0, 1, 2, 1];
+        assert_eq!(to_pbm(&image, 2), "P1\n2 2\n0 1\n0 1\n");
+        assert_eq!(to_pgm(&image, 2), "P2\n2 2\n255\n0 255\n128 255\n");
+    }
+
+    #[test]
+    fn decode_and_read_message() {
+        let message = ocr::draw_letters("HELLO").unwrap();
+        let front: String = message
+            .iter()
+            .flatten()
+            .map(|&lit| if lit { '2' } else { '0' })
+            .collect();
+        let back: String = message
+            .iter()
+            .flatten()
+            .map(|&lit| if lit { '1' } else { '2' })
+            .collect();
+        let layers = parse_layers(&(front + &back), WIDTH, HEIGHT);
+        let image = decode(&layers);
+        let pixels = to_pixels(&image, WIDTH);
+        assert_eq!(ocr::read_letters(&pixels), Some(String::from("HELLO")));
+    }
+}
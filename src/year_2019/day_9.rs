@@ -0,0 +1,64 @@
+use crate::year_2019::intcode::{parse_program, Intcode};
+use std::fs;
+
+#[allow(dead_code)]
+pub fn part_1() {
+    let location = "input/year_2019/day_9_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let outputs = run_boost(&parse_program(&input), 1);
+    // Any output before the keycode names an opcode that is not working.
+    println!("BOOST keycode: {:?}", outputs);
+}
+
+#[allow(dead_code)]
+pub fn part_2() {
+    let location = "input/year_2019/day_9_1.txt";
+    let input = fs::read_to_string(location).expect("Cannot read input file");
+    let outputs = run_boost(&parse_program(&input), 2);
+    println!("Distress signal coordinates: {:?}", outputs);
+}
+
+/// Runs the BOOST program in test mode (1) or sensor boost mode (2).
+fn run_boost(program: &[i64], mode: i64) -> Vec<i64> {
+    let mut intcode = Intcode::new(program.to_vec()).expect("Program cannot be empty");
+    intcode
+        .reset()
+        .push_input(mode)
+        .run()
+        .expect("BOOST program failed");
+    intcode.take_outputs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_9_1() {
+        let program = vec![
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        assert_eq!(run_boost(&program, 1), program);
+    }
+
+    #[test]
+    fn example_9_2() {
+        let program = vec![1102, 34915192, 34915192, 7, 4, 7, 99, 0];
+        let outputs = run_boost(&program, 1);
+        assert_eq!(outputs[0].to_string().len(), 16);
+    }
+
+    #[test]
+    fn example_9_3() {
+        let program = vec![104, 1125899906842624, 99];
+        assert_eq!(run_boost(&program, 1), vec![1125899906842624]);
+    }
+
+    #[test]
+    fn relative_base_can_move_backwards() {
+        // Stores the input past the end of the program through a relative
+        // write, then outputs it after moving the base back.
+        let program = vec![109, 2000, 203, 19, 109, -1000, 204, 1019, 99];
+        assert_eq!(run_boost(&program, 42), vec![42]);
+    }
+}
@@ -0,0 +1,50 @@
+use super::Intcode;
+
+/// Queues `line` as ASCII codes followed by a newline.
+pub fn push_line(intcode: &mut Intcode, line: &str) {
+    for byte in line.bytes() {
+        intcode.push_input(byte as i64);
+    }
+    intcode.push_input(b'\n' as i64);
+}
+
+/// Takes all pending outputs and splits them into the text made of ASCII
+/// codes and the values outside of the ASCII range, which programs use to
+/// report results.
+pub fn take_text(intcode: &mut Intcode) -> (String, Vec<i64>) {
+    let mut text = String::new();
+    let mut values = vec![];
+    for output in intcode.take_outputs() {
+        match output {
+            0..=127 => text.push(output as u8 as char),
+            _ => values.push(output),
+        }
+    }
+    (text, values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_are_pushed_as_codes() {
+        // Echoes three inputs.
+        let program = vec![3, 100, 4, 100, 3, 100, 4, 100, 3, 100, 4, 100, 99];
+        let mut intcode = Intcode::new(program).unwrap();
+        intcode.reset();
+        push_line(&mut intcode, "ok");
+        intcode.run().unwrap();
+        assert_eq!(take_text(&mut intcode), (String::from("ok\n"), vec![]));
+    }
+
+    #[test]
+    fn large_values_are_split_from_text() {
+        let program = vec![104, 72, 104, 105, 104, 10, 104, 12345, 99];
+        let mut intcode = Intcode::new(program).unwrap();
+        intcode.reset().run().unwrap();
+        let (text, values) = take_text(&mut intcode);
+        assert_eq!(text, "Hi\n");
+        assert_eq!(values, vec![12345]);
+    }
+}
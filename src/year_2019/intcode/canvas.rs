@@ -0,0 +1,327 @@
+use super::{Intcode, IntcodeError, Status};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// How a tile value is drawn in the terminal and in image files.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tile {
+    pub glyph: char,
+    pub color: [u8; 3],
+}
+
+pub struct Palette {
+    tiles: HashMap<i64, Tile>,
+    unknown: Tile,
+}
+
+impl Palette {
+    /// A palette that draws every tile as `unknown` until tiles are added.
+    pub fn new(unknown: Tile) -> Self {
+        Palette {
+            tiles: HashMap::new(),
+            unknown,
+        }
+    }
+
+    pub fn with(mut self, value: i64, glyph: char, color: [u8; 3]) -> Self {
+        self.tiles.insert(value, Tile { glyph, color });
+        self
+    }
+
+    pub fn tile(&self, value: Option<i64>) -> Tile {
+        value
+            .and_then(|v| self.tiles.get(&v))
+            .copied()
+            .unwrap_or(self.unknown)
+    }
+}
+
+impl Default for Palette {
+    /// Black and white, with 0 drawn as empty and 1 as a filled block.
+    fn default() -> Self {
+        Palette::new(Tile {
+            glyph: ' ',
+            color: [0, 0, 0],
+        })
+        .with(0, ' ', [0, 0, 0])
+        .with(1, '█', [255, 255, 255])
+    }
+}
+
+/// A sparse, unbounded grid of tile values.
+pub struct Canvas {
+    tiles: HashMap<(i64, i64), i64>,
+    palette: Palette,
+    frames: usize,
+}
+
+impl Canvas {
+    pub fn new(palette: Palette) -> Self {
+        Canvas {
+            tiles: HashMap::new(),
+            palette,
+            frames: 0,
+        }
+    }
+
+    pub fn paint(&mut self, x: i64, y: i64, value: i64) {
+        self.tiles.insert((x, y), value);
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> Option<i64> {
+        self.tiles.get(&(x, y)).copied()
+    }
+
+    pub fn remove(&mut self, x: i64, y: i64) -> Option<i64> {
+        self.tiles.remove(&(x, y))
+    }
+
+    /// Number of painted positions.
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    pub fn count(&self, value: i64) -> usize {
+        self.tiles.values().filter(|&&v| v == value).count()
+    }
+
+    pub fn find(&self, value: i64) -> Option<(i64, i64)> {
+        self.tiles
+            .iter()
+            .find(|(_, &v)| v == value)
+            .map(|(&position, _)| position)
+    }
+
+    /// The smallest and largest painted coordinates.
+    pub fn bounds(&self) -> Option<((i64, i64), (i64, i64))> {
+        if self.tiles.is_empty() {
+            return None;
+        }
+        let xs = self.tiles.keys().map(|p| p.0);
+        let ys = self.tiles.keys().map(|p| p.1);
+        Some((
+            (xs.clone().min()?, ys.clone().min()?),
+            (xs.max()?, ys.max()?),
+        ))
+    }
+
+    fn rows(&self) -> Vec<Vec<Tile>> {
+        match self.bounds() {
+            Some(((min_x, min_y), (max_x, max_y))) => (min_y..=max_y)
+                .map(|y| {
+                    (min_x..=max_x)
+                        .map(|x| self.palette.tile(self.get(x, y)))
+                        .collect()
+                })
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// The painted area as rows of flags that are set where the tile is
+    /// `value`, for example to read letters with `ocr::read_letters`.
+    pub fn mask(&self, value: i64) -> Vec<Vec<bool>> {
+        match self.bounds() {
+            Some(((min_x, min_y), (max_x, max_y))) => (min_y..=max_y)
+                .map(|y| {
+                    (min_x..=max_x)
+                        .map(|x| self.get(x, y) == Some(value))
+                        .collect()
+                })
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Draws the painted area as text, one line per row with y growing
+    /// downwards.
+    pub fn render(&self) -> String {
+        self.rows()
+            .iter()
+            .map(|row| row.iter().map(|t| t.glyph).collect::<String>() + "\n")
+            .collect()
+    }
+
+    /// Encodes the painted area as a binary PPM image, drawing each tile as
+    /// a `scale` by `scale` square.
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let rows = self.rows();
+        let height = rows.len() * scale;
+        let width = rows.first().map_or(0, |r| r.len()) * scale;
+        let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        for row in &rows {
+            for _ in 0..scale {
+                for tile in row {
+                    for _ in 0..scale {
+                        image.extend_from_slice(&tile.color);
+                    }
+                }
+            }
+        }
+        image
+    }
+
+    /// Clears the terminal and prints the current frame.
+    pub fn print_frame(&mut self) {
+        self.frames += 1;
+        print!("\x1b[2J\x1b[H{}", self.render());
+    }
+
+    /// Writes the current frame to `directory` as a numbered PPM file.
+    pub fn save_frame(&mut self, directory: &Path, scale: usize) -> io::Result<PathBuf> {
+        self.frames += 1;
+        let path = directory.join(format!("frame_{:05}.ppm", self.frames));
+        fs::write(&path, self.to_ppm(scale))?;
+        Ok(path)
+    }
+}
+
+/// Paints `(x, y, tile)` triples.
+pub fn draw_tiles(canvas: &mut Canvas, values: &[i64]) {
+    canvas.paint(values[0], values[1], values[2]);
+}
+
+/// Runs `intcode` with its outputs grouped into tuples of `size` that are
+/// handed to `draw` together with the canvas. Whenever the program asks for
+/// input, `controller` is called with the current canvas and its answer is
+/// fed back to the program. The run stops when the program halts, a limit is
+/// reached, or the controller returns `None`.
+pub fn play<D, C>(
+    intcode: &mut Intcode,
+    canvas: &mut Canvas,
+    size: usize,
+    mut draw: D,
+    mut controller: C,
+) -> Result<Status, IntcodeError>
+where
+    D: FnMut(&mut Canvas, &[i64]),
+    C: FnMut(&mut Canvas) -> Option<i64>,
+{
+    loop {
+        let status = intcode.run()?;
+        for values in intcode.take_tuples(size) {
+            draw(canvas, &values);
+        }
+        match status {
+            Status::AwaitingInput { .. } => match controller(canvas) {
+                Some(input) => {
+                    intcode.push_input(input);
+                }
+                None => return Ok(status),
+            },
+            status => return Ok(status),
+        }
+    }
+}
+
+/// A joystick for humans: prints the frame and reads `a`, `s` or `d` from
+/// stdin for left, neutral and right. Anything else ends the game.
+pub fn keyboard_joystick(canvas: &mut Canvas) -> Option<i64> {
+    canvas.print_frame();
+    println!("Joystick [a/s/d]: ");
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).ok()?;
+    match line.trim() {
+        "a" => Some(-1),
+        "s" | "" => Some(0),
+        "d" => Some(1),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_canvas() -> Canvas {
+        let mut canvas = Canvas::new(Palette::default());
+        canvas.paint(-1, 0, 1);
+        canvas.paint(1, 1, 1);
+        canvas.paint(0, 1, 0);
+        canvas
+    }
+
+    #[test]
+    fn bounds_cover_painted_tiles() {
+        let canvas = example_canvas();
+        assert_eq!(canvas.bounds(), Some(((-1, 0), (1, 1))));
+        assert_eq!(Canvas::new(Palette::default()).bounds(), None);
+    }
+
+    #[test]
+    fn render_uses_palette() {
+        let canvas = example_canvas();
+        assert_eq!(canvas.render(), "█  \n  █\n");
+        let palette = Palette::default().with(1, '#', [255, 0, 0]);
+        let mut canvas = Canvas::new(palette);
+        canvas.paint(0, 0, 1);
+        canvas.paint(1, 0, 2);
+        assert_eq!(canvas.render(), "# \n");
+    }
+
+    #[test]
+    fn ppm_has_header_and_scaled_pixels() {
+        let canvas = example_canvas();
+        let image = canvas.to_ppm(2);
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 6 * 4 * 3);
+        assert_eq!(&image[header.len()..header.len() + 3], &[255, 255, 255]);
+    }
+
+    #[test]
+    fn mask_marks_matching_tiles() {
+        let canvas = example_canvas();
+        assert_eq!(
+            canvas.mask(1),
+            vec![vec![true, false, false], vec![false, false, true]]
+        );
+    }
+
+    #[test]
+    fn count_and_find_tiles() {
+        let canvas = example_canvas();
+        assert_eq!(canvas.count(1), 2);
+        assert_eq!(canvas.find(0), Some((0, 1)));
+        assert_eq!(canvas.len(), 3);
+    }
+
+    // Paints (0, 0) with 1, reads a tile from the controller and paints it
+    // at (1, 0).
+    fn example_program() -> Vec<i64> {
+        vec![
+            104, 0, 104, 0, 104, 1, 3, 17, 104, 1, 104, 0, 4, 17, 99, 0, 0, 0,
+        ]
+    }
+
+    #[test]
+    fn controller_sees_canvas_and_feeds_input() {
+        let mut intcode = Intcode::new(example_program()).unwrap();
+        intcode.reset();
+        let mut canvas = Canvas::new(Palette::default());
+        let mut seen = vec![];
+        let status = play(&mut intcode, &mut canvas, 3, draw_tiles, |c| {
+            seen.push(c.get(0, 0));
+            Some(2)
+        });
+        assert_eq!(status, Ok(Status::Halted));
+        assert_eq!(seen, vec![Some(1)]);
+        assert_eq!(canvas.get(1, 0), Some(2));
+    }
+
+    #[test]
+    fn controller_can_stop_the_game() {
+        let mut intcode = Intcode::new(example_program()).unwrap();
+        intcode.reset();
+        let mut canvas = Canvas::new(Palette::default());
+        let status = play(&mut intcode, &mut canvas, 3, draw_tiles, |_| None);
+        assert_eq!(status, Ok(Status::AwaitingInput { pc: 6 }));
+        assert_eq!(canvas.len(), 1);
+    }
+}
@@ -0,0 +1,81 @@
+use num_bigint::BigInt;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// What an `Intcode` machine does when an addition or multiplication does
+/// not fit into its cell type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overflow {
+    /// Stop the machine with `IntcodeError::Overflow`.
+    Checked,
+    /// Wrap around at the boundary of the cell type.
+    Wrapping,
+}
+
+/// A value that can be stored in the memory of an `Intcode` machine.
+pub trait Cell: Clone + PartialEq + PartialOrd + fmt::Debug + fmt::Display + From<i64> {
+    fn add(&self, rhs: &Self, overflow: Overflow) -> Option<Self>;
+    fn mul(&self, rhs: &Self, overflow: Overflow) -> Option<Self>;
+    fn to_i64(&self) -> Option<i64>;
+}
+
+impl Cell for i64 {
+    fn add(&self, rhs: &Self, overflow: Overflow) -> Option<Self> {
+        match overflow {
+            Overflow::Checked => self.checked_add(*rhs),
+            Overflow::Wrapping => Some(self.wrapping_add(*rhs)),
+        }
+    }
+
+    fn mul(&self, rhs: &Self, overflow: Overflow) -> Option<Self> {
+        match overflow {
+            Overflow::Checked => self.checked_mul(*rhs),
+            Overflow::Wrapping => Some(self.wrapping_mul(*rhs)),
+        }
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        Some(*self)
+    }
+}
+
+/// Arbitrary precision cells never overflow, so the policy is ignored.
+impl Cell for BigInt {
+    fn add(&self, rhs: &Self, _overflow: Overflow) -> Option<Self> {
+        Some(self + rhs)
+    }
+
+    fn mul(&self, rhs: &Self, _overflow: Overflow) -> Option<Self> {
+        Some(self * rhs)
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_i64_detects_overflow() {
+        assert_eq!(i64::MAX.add(&1, Overflow::Checked), None);
+        assert_eq!(i64::MAX.mul(&2, Overflow::Checked), None);
+        assert_eq!(40.add(&2, Overflow::Checked), Some(42));
+    }
+
+    #[test]
+    fn wrapping_i64_wraps() {
+        assert_eq!(i64::MAX.add(&1, Overflow::Wrapping), Some(i64::MIN));
+        assert_eq!(i64::MAX.mul(&2, Overflow::Wrapping), Some(-2));
+    }
+
+    #[test]
+    fn big_int_grows() {
+        let max = BigInt::from(i64::MAX);
+        let sum = max.add(&BigInt::from(1), Overflow::Checked).unwrap();
+        assert_eq!(sum.to_string(), "9223372036854775808");
+        assert_eq!(sum.to_i64(), None);
+    }
+}
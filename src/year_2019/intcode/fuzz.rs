@@ -0,0 +1,433 @@
+use super::{Intcode, IntcodeError, Status as MachineStatus, MEMORY_LIMIT};
+
+/// Steps after which a program that has not halted is considered running.
+const MAX_STEPS: u64 = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Halted,
+    Running,
+    AwaitingInput,
+    Failed(IntcodeError),
+}
+
+/// The observable state of a machine after a bounded run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub memory: Vec<i64>,
+    pub outputs: Vec<i64>,
+    pub status: Status,
+}
+
+/// Runs `program` with `inputs` for at most `max_steps` instructions.
+pub type Interpreter = fn(&[i64], &[i64], u64) -> Outcome;
+
+/// A program on which an interpreter disagrees with the reference
+/// implementation, shrunk as far as possible.
+#[derive(Debug)]
+pub struct Failure {
+    pub program: Vec<i64>,
+    pub inputs: Vec<i64>,
+    pub expected: Outcome,
+    pub actual: Outcome,
+}
+
+pub fn run_production(program: &[i64], inputs: &[i64], max_steps: u64) -> Outcome {
+    let mut intcode = Intcode::new(program.to_vec()).expect("Program must not be empty");
+    intcode.reset().limit_steps(max_steps);
+    for &input in inputs {
+        intcode.push_input(input);
+    }
+    let status = match intcode.run() {
+        Ok(MachineStatus::Halted) => Status::Halted,
+        Ok(MachineStatus::AwaitingInput { .. }) => Status::AwaitingInput,
+        Ok(_) => Status::Running,
+        Err(error) => Status::Failed(error),
+    };
+    Outcome {
+        memory: intcode.dump(),
+        outputs: intcode.take_outputs(),
+        status,
+    }
+}
+
+/// A deliberately straightforward interpreter that the production `Intcode`
+/// is checked against.
+pub fn run_reference(program: &[i64], inputs: &[i64], max_steps: u64) -> Outcome {
+    let mut memory = program.to_vec();
+    let mut inputs = inputs.iter().copied();
+    let mut outputs = vec![];
+    let mut pc = 0;
+    let mut relative_base = 0;
+    let mut status = Status::Running;
+    for _ in 0..max_steps {
+        let step = reference_step(
+            &mut memory,
+            &mut pc,
+            &mut relative_base,
+            &mut inputs,
+            &mut outputs,
+        );
+        match step {
+            Ok(Status::Running) => (),
+            Ok(stopped) => {
+                status = stopped;
+                break;
+            }
+            Err(error) => {
+                status = Status::Failed(error);
+                break;
+            }
+        }
+    }
+    Outcome {
+        memory,
+        outputs,
+        status,
+    }
+}
+
+/// Executes one instruction, unless it is a halt or an input that cannot be
+/// satisfied.
+fn reference_step(
+    memory: &mut Vec<i64>,
+    pc: &mut usize,
+    relative_base: &mut i64,
+    inputs: &mut dyn Iterator<Item = i64>,
+    outputs: &mut Vec<i64>,
+) -> Result<Status, IntcodeError> {
+    let at = *pc;
+    // Memory reads as zero past the end and grows on first use.
+    let grow = |memory: &mut Vec<i64>, address: usize| {
+        if MEMORY_LIMIT <= address {
+            return Err(IntcodeError::InvalidAddress { pc: at });
+        }
+        if memory.len() <= address {
+            memory.resize(address + 1, 0);
+        }
+        Ok(())
+    };
+    grow(memory, at)?;
+    let instruction = memory[at];
+    let opcode = instruction % 100;
+    let (count, writes) = match opcode {
+        1 | 2 | 7 | 8 => (3, Some(3)),
+        3 => (1, Some(1)),
+        4 | 9 => (1, None),
+        5 | 6 => (2, None),
+        99 => return Ok(Status::Halted),
+        _ => return Err(IntcodeError::UnknownOpcode { pc: at }),
+    };
+    let mut addresses = vec![];
+    for n in 1..=count {
+        let mode = instruction / 10_i64.pow(n as u32 + 1) % 10;
+        let address = match mode {
+            0 | 2 => {
+                grow(memory, at + n)?;
+                let base = if mode == 2 { *relative_base } else { 0 };
+                match memory[at + n].checked_add(base) {
+                    Some(address) if 0 <= address => address as usize,
+                    _ => return Err(IntcodeError::InvalidAddress { pc: at }),
+                }
+            }
+            1 if writes != Some(n) => at + n,
+            _ => return Err(IntcodeError::InvalidMode { pc: at }),
+        };
+        grow(memory, address)?;
+        addresses.push(address);
+    }
+    match opcode {
+        1 => {
+            memory[addresses[2]] = memory[addresses[0]]
+                .checked_add(memory[addresses[1]])
+                .ok_or(IntcodeError::Overflow { pc: at })?;
+        }
+        2 => {
+            memory[addresses[2]] = memory[addresses[0]]
+                .checked_mul(memory[addresses[1]])
+                .ok_or(IntcodeError::Overflow { pc: at })?;
+        }
+        3 => {
+            memory[addresses[0]] = match inputs.next() {
+                Some(input) => input,
+                None => return Ok(Status::AwaitingInput),
+            };
+        }
+        4 => outputs.push(memory[addresses[0]]),
+        5 | 6 => {
+            if (memory[addresses[0]] != 0) == (opcode == 5) {
+                let target = memory[addresses[1]];
+                if target < 0 {
+                    return Err(IntcodeError::InvalidAddress { pc: at });
+                }
+                *pc = target as usize;
+                return Ok(Status::Running);
+            }
+        }
+        7 => memory[addresses[2]] = (memory[addresses[0]] < memory[addresses[1]]) as i64,
+        8 => memory[addresses[2]] = (memory[addresses[0]] == memory[addresses[1]]) as i64,
+        _ => {
+            *relative_base = relative_base
+                .checked_add(memory[addresses[0]])
+                .ok_or(IntcodeError::Overflow { pc: at })?;
+        }
+    }
+    *pc += count + 1;
+    Ok(Status::Running)
+}
+
+/// A small xorshift generator, so that fuzzing runs are reproducible from a
+/// seed without pulling in a dependency.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng {
+            state: (seed ^ 0x9e37_79b9_7f4a_7c15).max(1),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low + 1) as u64) as i64
+    }
+}
+
+/// Generates a program made of valid instructions that ends in a halt,
+/// followed by a few data cells. Addresses may point anywhere inside the
+/// program or a little past its end, so the program can still modify itself
+/// into something invalid.
+pub fn generate_well_formed(rng: &mut Rng) -> Vec<i64> {
+    let opcodes: Vec<i64> = (0..rng.range(1, 8)).map(|_| rng.range(1, 9)).collect();
+    // Number of parameters read and written by each opcode.
+    let params = |opcode: i64| match opcode {
+        1 | 2 | 7 | 8 => (2, 1),
+        3 => (0, 1),
+        4 | 9 => (1, 0),
+        _ => (2, 0),
+    };
+    let code_len = opcodes
+        .iter()
+        .map(|&o| {
+            let (reads, writes) = params(o);
+            1 + reads + writes
+        })
+        .sum::<usize>()
+        + 1;
+    let len = code_len + rng.range(1, 6) as usize;
+    let address = |rng: &mut Rng| rng.range(0, len as i64 + 10);
+    let mut program = vec![];
+    for opcode in opcodes {
+        let (reads, writes) = params(opcode);
+        let mut instruction = opcode;
+        let mut values = vec![];
+        for n in 0..reads + writes {
+            let mode = if n < reads {
+                rng.below(3)
+            } else {
+                2 * rng.below(2)
+            };
+            instruction += 100 * 10_i64.pow(n as u32) * mode as i64;
+            values.push(match mode {
+                1 => rng.range(-100, 100),
+                2 => rng.range(-5, 5),
+                _ => address(rng),
+            });
+        }
+        program.push(instruction);
+        program.extend(values);
+    }
+    program.push(99);
+    while program.len() < len {
+        program.push(rng.range(-1000, 1000));
+    }
+    program
+}
+
+/// Generates a well-formed program and then breaks it in a few random places.
+pub fn generate_malformed(rng: &mut Rng) -> Vec<i64> {
+    let mut program = generate_well_formed(rng);
+    for _ in 0..rng.range(1, 3) {
+        let index = rng.below(program.len() as u64) as usize;
+        match rng.below(5) {
+            0 => program[index] = rng.range(-200, 200),
+            1 => program[index] = program[index] % 100 + 100 * rng.range(2, 999),
+            2 => {
+                program[index] = if rng.below(2) == 1 {
+                    i64::MAX
+                } else {
+                    i64::MIN
+                }
+            }
+            3 => program[index] = -rng.range(1, 10),
+            _ => program.truncate(index.max(1)),
+        }
+        if program.len() <= index {
+            break;
+        }
+    }
+    program
+}
+
+/// Removes cells and simplifies values of `program` for as long as `fails`
+/// keeps returning true, and returns the smallest failing program found.
+pub fn shrink<F: Fn(&[i64]) -> bool>(program: &[i64], fails: F) -> Vec<i64> {
+    let mut current = program.to_vec();
+    loop {
+        let mut improved = false;
+        let mut chunk = current.len() / 2;
+        while 0 < chunk {
+            let mut start = 0;
+            while chunk < current.len() && start + chunk <= current.len() {
+                let mut candidate = current.clone();
+                candidate.drain(start..start + chunk);
+                if fails(&candidate) {
+                    current = candidate;
+                    improved = true;
+                } else {
+                    start += chunk;
+                }
+            }
+            chunk /= 2;
+        }
+        for index in 0..current.len() {
+            for value in simpler_values(current[index]) {
+                let mut candidate = current.clone();
+                candidate[index] = value;
+                if fails(&candidate) {
+                    current = candidate;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+        if !improved {
+            return current;
+        }
+    }
+}
+
+fn simpler_values(value: i64) -> Vec<i64> {
+    let mut values = vec![];
+    if value != 0 {
+        values.push(0);
+    }
+    if 1 < value.abs() {
+        values.push(value / 2);
+    }
+    if let Some(negated) = value.checked_neg().filter(|_| value < 0) {
+        values.push(negated);
+    }
+    values
+}
+
+fn differs(candidate: Interpreter, program: &[i64], inputs: &[i64]) -> bool {
+    !program.is_empty()
+        && candidate(program, inputs, MAX_STEPS) != run_reference(program, inputs, MAX_STEPS)
+}
+
+/// Runs `iterations` random programs on the production `Intcode` and on the
+/// reference interpreter and returns the first disagreement.
+pub fn fuzz(seed: u64, iterations: usize) -> Result<(), Box<Failure>> {
+    fuzz_against(run_production, seed, iterations)
+}
+
+pub fn fuzz_against(
+    candidate: Interpreter,
+    seed: u64,
+    iterations: usize,
+) -> Result<(), Box<Failure>> {
+    let mut rng = Rng::new(seed);
+    for iteration in 0..iterations {
+        let program = if iteration % 2 == 0 {
+            generate_well_formed(&mut rng)
+        } else {
+            generate_malformed(&mut rng)
+        };
+        let inputs: Vec<i64> = (0..rng.range(0, 3))
+            .map(|_| rng.range(-1000, 1000))
+            .collect();
+        if differs(candidate, &program, &inputs) {
+            let program = shrink(&program, |p| differs(candidate, p, &inputs));
+            return Err(Box::new(Failure {
+                expected: run_reference(&program, &inputs, MAX_STEPS),
+                actual: candidate(&program, &inputs, MAX_STEPS),
+                program,
+                inputs,
+            }));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_runs_examples() {
+        let outcome = run_reference(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50], &[], 10);
+        assert_eq!(outcome.memory[0], 3500);
+        assert_eq!(outcome.status, Status::Halted);
+        let outcome = run_reference(&[3, 0, 4, 0, 99], &[42], 10);
+        assert_eq!(outcome.outputs, vec![42]);
+    }
+
+    #[test]
+    fn bounded_run_reports_running() {
+        let outcome = run_production(&[1101, 0, 0, 0, 99], &[], 1);
+        assert_eq!(outcome.status, Status::Running);
+    }
+
+    #[test]
+    fn production_matches_reference() {
+        if let Err(failure) = fuzz(2019, 2000) {
+            panic!("Interpreters disagree: {:?}", failure);
+        }
+    }
+
+    #[test]
+    fn shrink_finds_minimal_program() {
+        let program = vec![5, 3, 7, 12, 99, 0, -4];
+        let shrunk = shrink(&program, |p| p.contains(&7) && 2 <= p.len());
+        assert_eq!(shrunk.len(), 2);
+        assert!(shrunk.contains(&7));
+    }
+
+    fn misreports_large_outputs(program: &[i64], inputs: &[i64], max_steps: u64) -> Outcome {
+        let mut outcome = run_reference(program, inputs, max_steps);
+        outcome
+            .outputs
+            .iter_mut()
+            .filter(|o| 100 < **o)
+            .for_each(|o| *o += 1);
+        outcome
+    }
+
+    #[test]
+    fn fuzzer_finds_and_shrinks_bug() {
+        let failure = fuzz_against(misreports_large_outputs, 7, 1000).unwrap_err();
+        assert_ne!(failure.expected, failure.actual);
+        for index in 0..failure.program.len() {
+            let mut smaller = failure.program.clone();
+            smaller.remove(index);
+            assert!(!differs(
+                misreports_large_outputs,
+                &smaller,
+                &failure.inputs
+            ));
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::VecDeque;
+
+/// Everything a single step changed, so that the step can be undone.
+pub struct Delta<T> {
+    pub pc: usize,
+    pub relative_base: i64,
+    pub halt: bool,
+    pub write: Option<(usize, T)>,
+    pub input: Option<T>,
+    pub output: bool,
+}
+
+/// A bounded log of the most recent steps of an `Intcode` machine.
+pub struct History<T> {
+    window: usize,
+    deltas: VecDeque<Delta<T>>,
+}
+
+impl<T> History<T> {
+    pub fn new(window: usize) -> Self {
+        History {
+            window,
+            deltas: VecDeque::with_capacity(window),
+        }
+    }
+
+    pub fn push(&mut self, delta: Delta<T>) {
+        if self.window == 0 {
+            return;
+        }
+        if self.deltas.len() == self.window {
+            self.deltas.pop_front();
+        }
+        self.deltas.push_back(delta);
+    }
+
+    pub fn pop(&mut self) -> Option<Delta<T>> {
+        self.deltas.pop_back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.deltas.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.deltas.clear();
+    }
+
+    /// Number of steps that have to be undone to get back to the step that
+    /// last wrote to `address`, if that step is still inside the window.
+    pub fn steps_since_write(&self, address: usize) -> Option<usize> {
+        self.deltas
+            .iter()
+            .rev()
+            .position(|d| matches!(d.write, Some((a, _)) if a == address))
+            .map(|p| p + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delta(pc: usize, write: Option<(usize, i64)>) -> Delta<i64> {
+        Delta {
+            pc,
+            relative_base: 0,
+            halt: false,
+            write,
+            input: None,
+            output: false,
+        }
+    }
+
+    #[test]
+    fn window_drops_oldest_delta() {
+        let mut history = History::new(2);
+        history.push(delta(0, None));
+        history.push(delta(4, None));
+        history.push(delta(8, None));
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.pop().unwrap().pc, 8);
+        assert_eq!(history.pop().unwrap().pc, 4);
+        assert!(history.pop().is_none());
+    }
+
+    #[test]
+    fn zero_window_records_nothing() {
+        let mut history = History::new(0);
+        history.push(delta(0, None));
+        assert_eq!(history.len(), 0);
+    }
+
+    #[test]
+    fn finds_last_write() {
+        let mut history = History::new(10);
+        history.push(delta(0, Some((3, 1))));
+        history.push(delta(4, Some((5, 2))));
+        history.push(delta(8, Some((3, 7))));
+        history.push(delta(12, None));
+        assert_eq!(history.steps_since_write(3), Some(2));
+        assert_eq!(history.steps_since_write(5), Some(3));
+        assert_eq!(history.steps_since_write(6), None);
+    }
+}
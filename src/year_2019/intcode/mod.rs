@@ -0,0 +1,879 @@
+pub mod ascii;
+pub mod canvas;
+mod cell;
+pub mod fuzz;
+mod history;
+
+pub use cell::{Cell, Overflow};
+use history::{Delta, History};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntcodeError {
+    /// An addition or multiplication did not fit into the cell type.
+    Overflow { pc: usize },
+    /// The instruction at `pc` is not a known opcode.
+    UnknownOpcode { pc: usize },
+    /// A parameter of the instruction at `pc` uses an unsupported mode.
+    InvalidMode { pc: usize },
+    /// The instruction at `pc` refers to a negative address or to one beyond
+    /// the memory limit.
+    InvalidAddress { pc: usize },
+    /// The requested step is not inside the recorded history window.
+    NotRecorded { step: u64 },
+    /// The program halted before the requested step was reached.
+    Halted { pc: usize },
+}
+
+/// Number of cells memory may grow to unless a different limit is set.
+pub const MEMORY_LIMIT: usize = 1 << 20;
+
+/// Parses a comma separated Intcode program.
+pub fn parse_program(input: &str) -> Vec<i64> {
+    input
+        .trim()
+        .split(',')
+        .map(|s| s.trim().parse::<i64>().expect("Cannot parse program"))
+        .collect()
+}
+
+/// Why a machine stopped executing instructions without failing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    /// The machine can execute further instructions.
+    Running,
+    /// The program executed a halt instruction.
+    Halted,
+    /// The instruction at `pc` reads input but the input queue is empty.
+    /// Push input to resume.
+    AwaitingInput { pc: usize },
+    /// The step budget is used up. Grant more steps to resume.
+    OutOfSteps { pc: usize, steps: u64 },
+    /// The deadline has passed. Set a new one to resume.
+    DeadlineExceeded { pc: usize, steps: u64 },
+}
+
+pub struct Intcode<T: Cell = i64> {
+    rom: Vec<T>,
+    memory: Vec<T>,
+    pc: usize,
+    relative_base: i64,
+    halt: bool,
+    steps: u64,
+    overflow: Overflow,
+    memory_limit: usize,
+    step_budget: Option<u64>,
+    deadline: Option<Instant>,
+    inputs: VecDeque<T>,
+    outputs: Vec<T>,
+    outputs_read: usize,
+    history: Option<History<T>>,
+}
+
+impl<T: Cell> Intcode<T> {
+    pub fn new(initial: Vec<T>) -> Result<Self, &'static str> {
+        if initial.is_empty() {
+            Err("Initial memory must contain data.")
+        } else {
+            Ok(Intcode {
+                rom: initial,
+                memory: vec![],
+                pc: 0,
+                relative_base: 0,
+                halt: true,
+                steps: 0,
+                overflow: Overflow::Checked,
+                memory_limit: MEMORY_LIMIT,
+                step_budget: None,
+                deadline: None,
+                inputs: VecDeque::new(),
+                outputs: vec![],
+                outputs_read: 0,
+                history: None,
+            })
+        }
+    }
+
+    pub fn read_output(&self) -> T {
+        self.memory[0].clone()
+    }
+
+    pub fn write_noun(&mut self, noun: T) -> bool {
+        let mut write_sucessful = false;
+        if !self.memory.is_empty() {
+            self.memory[1] = noun;
+            write_sucessful = true;
+        }
+        write_sucessful
+    }
+
+    pub fn write_verb(&mut self, verb: T) -> bool {
+        let mut write_sucessful = false;
+        if 2 <= self.memory.len() {
+            self.memory[2] = verb;
+            write_sucessful = true;
+        }
+        write_sucessful
+    }
+
+    pub fn flash(&mut self, new_rom: Vec<T>) {
+        self.rom = new_rom;
+    }
+
+    pub fn dump(&self) -> Vec<T> {
+        self.memory.clone()
+    }
+
+    /// Sets what happens when an addition or multiplication overflows the
+    /// cell type. Machines check for overflow by default.
+    pub fn overflow(&mut self, overflow: Overflow) -> &mut Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Sets how many cells memory may grow to. Addresses past the end of the
+    /// program read as zero and grow memory when they are first used.
+    pub fn memory_limit(&mut self, cells: usize) -> &mut Self {
+        self.memory_limit = cells;
+        self
+    }
+
+    /// Allows the machine to execute `steps` more instructions before it
+    /// stops with `Status::OutOfSteps`. Limits are kept across resets, and
+    /// steps undone with `step_back` can be executed again.
+    pub fn limit_steps(&mut self, steps: u64) -> &mut Self {
+        self.step_budget = Some(steps);
+        self
+    }
+
+    /// Adds `steps` to the remaining step budget, if there is one.
+    pub fn grant_steps(&mut self, steps: u64) -> &mut Self {
+        if let Some(budget) = &mut self.step_budget {
+            *budget += steps;
+        }
+        self
+    }
+
+    /// Stops the machine with `Status::DeadlineExceeded` once `deadline` has
+    /// passed.
+    pub fn deadline(&mut self, deadline: Instant) -> &mut Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn time_limit(&mut self, limit: Duration) -> &mut Self {
+        self.deadline(Instant::now() + limit)
+    }
+
+    pub fn clear_limits(&mut self) -> &mut Self {
+        self.step_budget = None;
+        self.deadline = None;
+        self
+    }
+
+    pub fn reset(&mut self) -> &mut Self {
+        self.memory = self.rom.clone();
+        self.pc = 0;
+        self.relative_base = 0;
+        self.halt = false;
+        self.steps = 0;
+        self.inputs.clear();
+        self.outputs.clear();
+        self.outputs_read = 0;
+        if let Some(history) = &mut self.history {
+            history.clear();
+        }
+        self
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halt
+    }
+
+    /// Queues a value for the next input instruction.
+    pub fn push_input(&mut self, value: T) -> &mut Self {
+        self.inputs.push_back(value);
+        self
+    }
+
+    /// Returns the oldest output that has not been taken yet.
+    pub fn take_output(&mut self) -> Option<T> {
+        let output = self.outputs.get(self.outputs_read).cloned();
+        if output.is_some() {
+            self.outputs_read += 1;
+        }
+        output
+    }
+
+    /// Returns the outputs that have not been taken yet in groups of `size`.
+    /// An incomplete group at the end is left for a later call.
+    pub fn take_tuples(&mut self, size: usize) -> Vec<Vec<T>> {
+        let pending = &self.outputs[self.outputs_read..];
+        let tuples: Vec<Vec<T>> = pending.chunks_exact(size).map(|c| c.to_vec()).collect();
+        self.outputs_read += tuples.len() * size;
+        tuples
+    }
+
+    /// Returns all outputs that have not been taken yet.
+    pub fn take_outputs(&mut self) -> Vec<T> {
+        let outputs = self.outputs[self.outputs_read..].to_vec();
+        self.outputs_read = self.outputs.len();
+        outputs
+    }
+
+    /// Starts logging the changes made by each step so that the machine can
+    /// be stepped backwards. Only the last `window` steps are kept.
+    pub fn record(&mut self, window: usize) -> &mut Self {
+        self.history = Some(History::new(window));
+        self
+    }
+
+    pub fn stop_recording(&mut self) -> &mut Self {
+        self.history = None;
+        self
+    }
+
+    /// Executes a single instruction unless the machine has halted or one of
+    /// its limits has been reached.
+    pub fn step(&mut self) -> Result<Status, IntcodeError> {
+        if self.halt {
+            return Ok(Status::Halted);
+        }
+        if self.step_budget == Some(0) {
+            return Ok(Status::OutOfSteps {
+                pc: self.pc,
+                steps: self.steps,
+            });
+        }
+        if matches!(self.deadline, Some(deadline) if deadline <= Instant::now()) {
+            return Ok(Status::DeadlineExceeded {
+                pc: self.pc,
+                steps: self.steps,
+            });
+        }
+        let opcode = self.read_opcode()?;
+        if let (Opcode::Input(_), None) = (&opcode, self.inputs.front()) {
+            return Ok(Status::AwaitingInput { pc: self.pc });
+        }
+        let delta = if self.history.is_some() {
+            Some(Delta {
+                pc: self.pc,
+                relative_base: self.relative_base,
+                halt: self.halt,
+                write: opcode.dst().map(|dst| (dst, self.memory[dst].clone())),
+                input: match opcode {
+                    Opcode::Input(_) => self.inputs.front().cloned(),
+                    _ => None,
+                },
+                output: matches!(opcode, Opcode::Output(_)),
+            })
+        } else {
+            None
+        };
+        self.process_opcode(&opcode)?;
+        if let (Some(history), Some(delta)) = (&mut self.history, delta) {
+            history.push(delta);
+        }
+        self.steps += 1;
+        if let Some(budget) = &mut self.step_budget {
+            *budget -= 1;
+        }
+        if self.halt {
+            Ok(Status::Halted)
+        } else {
+            Ok(Status::Running)
+        }
+    }
+
+    /// Undoes the last recorded step. Returns false if there is nothing left
+    /// to undo in the history window. Consumed input goes back to the front
+    /// of the input queue and produced output is removed again, even if it
+    /// has already been taken. The undone step is added back to the step
+    /// budget. Memory that has grown stays allocated.
+    pub fn step_back(&mut self) -> bool {
+        let delta = match self.history.as_mut().and_then(|h| h.pop()) {
+            Some(delta) => delta,
+            None => return false,
+        };
+        if let Some((address, value)) = delta.write {
+            self.memory[address] = value;
+        }
+        if let Some(input) = delta.input {
+            self.inputs.push_front(input);
+        }
+        if delta.output {
+            self.outputs.pop();
+            self.outputs_read = usize::min(self.outputs_read, self.outputs.len());
+        }
+        self.pc = delta.pc;
+        self.relative_base = delta.relative_base;
+        self.halt = delta.halt;
+        self.steps -= 1;
+        if let Some(budget) = &mut self.step_budget {
+            *budget += 1;
+        }
+        true
+    }
+
+    /// Steps backwards until the instruction that last wrote to `address` is
+    /// the next one to be executed. The machine is left untouched if no such
+    /// write is recorded.
+    pub fn rewind_to_last_write(&mut self, address: usize) -> bool {
+        let count = match self
+            .history
+            .as_ref()
+            .and_then(|h| h.steps_since_write(address))
+        {
+            Some(count) => count,
+            None => return false,
+        };
+        for _ in 0..count {
+            self.step_back();
+        }
+        true
+    }
+
+    /// Moves the machine to the state right before instruction number `n` is
+    /// executed, stepping forwards or backwards as needed. Stepping forwards
+    /// stops early if a limit is reached.
+    pub fn goto_step(&mut self, n: u64) -> Result<Status, IntcodeError> {
+        if n < self.steps {
+            let recorded = self.history.as_ref().map_or(0, |h| h.len()) as u64;
+            if recorded < self.steps - n {
+                return Err(IntcodeError::NotRecorded { step: n });
+            }
+            while n < self.steps {
+                self.step_back();
+            }
+        } else {
+            while self.steps < n {
+                match self.step()? {
+                    Status::Running => (),
+                    Status::Halted if self.steps == n => return Ok(Status::Halted),
+                    Status::Halted => return Err(IntcodeError::Halted { pc: self.pc }),
+                    status => return Ok(status),
+                }
+            }
+        }
+        Ok(Status::Running)
+    }
+
+    /// Makes `address` usable, growing memory with zeros up to the memory
+    /// limit.
+    fn ensure(&mut self, address: usize) -> Result<(), IntcodeError> {
+        if self.memory.len() <= address {
+            if self.memory_limit <= address {
+                return Err(IntcodeError::InvalidAddress { pc: self.pc });
+            }
+            self.memory.resize(address + 1, T::from(0));
+        }
+        Ok(())
+    }
+
+    /// Address of the parameter at `offset` after the opcode, read in
+    /// position (0), immediate (1) or relative (2) mode.
+    fn param(&mut self, offset: usize, mode: i64) -> Result<usize, IntcodeError> {
+        let index = self.pc + offset;
+        let base = match mode {
+            0 => 0,
+            1 => {
+                self.ensure(index)?;
+                return Ok(index);
+            }
+            2 => self.relative_base,
+            _ => return Err(IntcodeError::InvalidMode { pc: self.pc }),
+        };
+        self.ensure(index)?;
+        let address = self.memory[index]
+            .to_i64()
+            .and_then(|address| address.checked_add(base))
+            .filter(|address| 0 <= *address)
+            .map_or(usize::MAX, |address| address as usize);
+        self.ensure(address)?;
+        Ok(address)
+    }
+
+    /// Address of a parameter that is written to, which must not be in
+    /// immediate mode.
+    fn dst_param(&mut self, offset: usize, mode: i64) -> Result<usize, IntcodeError> {
+        match mode {
+            0 | 2 => self.param(offset, mode),
+            _ => Err(IntcodeError::InvalidMode { pc: self.pc }),
+        }
+    }
+
+    fn read_opcode(&mut self) -> Result<Opcode, IntcodeError> {
+        self.ensure(self.pc)?;
+        let opcode_id = self.memory[self.pc]
+            .to_i64()
+            .ok_or(IntcodeError::UnknownOpcode { pc: self.pc })?;
+        let mode1 = (opcode_id / 100) % 10;
+        let mode2 = (opcode_id / 1000) % 10;
+        let mode3 = (opcode_id / 10000) % 10;
+        match opcode_id % 100 {
+            1 => Ok(Opcode::Add(AddParams {
+                src1: self.param(1, mode1)?,
+                src2: self.param(2, mode2)?,
+                dst: self.dst_param(3, mode3)?,
+            })),
+            2 => Ok(Opcode::Mult(MultParams {
+                src1: self.param(1, mode1)?,
+                src2: self.param(2, mode2)?,
+                dst: self.dst_param(3, mode3)?,
+            })),
+            3 => Ok(Opcode::Input(InputParams {
+                dst: self.dst_param(1, mode1)?,
+            })),
+            4 => Ok(Opcode::Output(OutputParams {
+                src: self.param(1, mode1)?,
+            })),
+            5 => Ok(Opcode::JumpIfTrue(JumpParams {
+                cond: self.param(1, mode1)?,
+                target: self.param(2, mode2)?,
+            })),
+            6 => Ok(Opcode::JumpIfFalse(JumpParams {
+                cond: self.param(1, mode1)?,
+                target: self.param(2, mode2)?,
+            })),
+            7 => Ok(Opcode::LessThan(CompareParams {
+                src1: self.param(1, mode1)?,
+                src2: self.param(2, mode2)?,
+                dst: self.dst_param(3, mode3)?,
+            })),
+            8 => Ok(Opcode::Equals(CompareParams {
+                src1: self.param(1, mode1)?,
+                src2: self.param(2, mode2)?,
+                dst: self.dst_param(3, mode3)?,
+            })),
+            9 => Ok(Opcode::AdjustBase(AdjustBaseParams {
+                src: self.param(1, mode1)?,
+            })),
+            99 => Ok(Opcode::Halt),
+            _ => Err(IntcodeError::UnknownOpcode { pc: self.pc }),
+        }
+    }
+
+    fn process_opcode(&mut self, opcode: &Opcode) -> Result<(), IntcodeError> {
+        match opcode {
+            Opcode::Add(ref add_params) => {
+                self.memory[add_params.dst] = self.memory[add_params.src1]
+                    .add(&self.memory[add_params.src2], self.overflow)
+                    .ok_or(IntcodeError::Overflow { pc: self.pc })?;
+                self.pc += 4;
+            }
+            Opcode::Mult(ref mult_params) => {
+                self.memory[mult_params.dst] = self.memory[mult_params.src1]
+                    .mul(&self.memory[mult_params.src2], self.overflow)
+                    .ok_or(IntcodeError::Overflow { pc: self.pc })?;
+                self.pc += 4;
+            }
+            Opcode::Halt => {
+                self.halt = true;
+            }
+            Opcode::Input(ref input_params) => {
+                self.memory[input_params.dst] = self
+                    .inputs
+                    .pop_front()
+                    .expect("Input is checked before processing");
+                self.pc += 2;
+            }
+            Opcode::Output(ref output_params) => {
+                self.outputs.push(self.memory[output_params.src].clone());
+                self.pc += 2;
+            }
+            Opcode::JumpIfTrue(ref jump_params) => {
+                if self.memory[jump_params.cond] != T::from(0) {
+                    self.pc = self.jump_target(jump_params.target)?;
+                } else {
+                    self.pc += 3;
+                }
+            }
+            Opcode::JumpIfFalse(ref jump_params) => {
+                if self.memory[jump_params.cond] == T::from(0) {
+                    self.pc = self.jump_target(jump_params.target)?;
+                } else {
+                    self.pc += 3;
+                }
+            }
+            Opcode::LessThan(ref compare_params) => {
+                let less = self.memory[compare_params.src1] < self.memory[compare_params.src2];
+                self.memory[compare_params.dst] = T::from(less as i64);
+                self.pc += 4;
+            }
+            Opcode::Equals(ref compare_params) => {
+                let equal = self.memory[compare_params.src1] == self.memory[compare_params.src2];
+                self.memory[compare_params.dst] = T::from(equal as i64);
+                self.pc += 4;
+            }
+            Opcode::AdjustBase(ref adjust_params) => {
+                self.relative_base = self.memory[adjust_params.src]
+                    .to_i64()
+                    .and_then(|offset| self.relative_base.checked_add(offset))
+                    .ok_or(IntcodeError::Overflow { pc: self.pc })?;
+                self.pc += 2;
+            }
+        }
+        Ok(())
+    }
+
+    fn jump_target(&self, address: usize) -> Result<usize, IntcodeError> {
+        self.memory[address]
+            .to_i64()
+            .filter(|target| 0 <= *target)
+            .map(|target| target as usize)
+            .ok_or(IntcodeError::InvalidAddress { pc: self.pc })
+    }
+
+    /// Executes instructions until the program halts or a limit is reached.
+    pub fn run(&mut self) -> Result<Status, IntcodeError> {
+        loop {
+            match self.step()? {
+                Status::Running => (),
+                status => return Ok(status),
+            }
+        }
+    }
+}
+
+enum Opcode {
+    Add(AddParams),
+    Mult(MultParams),
+    Halt,
+    Input(InputParams),
+    Output(OutputParams),
+    JumpIfTrue(JumpParams),
+    JumpIfFalse(JumpParams),
+    LessThan(CompareParams),
+    Equals(CompareParams),
+    AdjustBase(AdjustBaseParams),
+}
+
+impl Opcode {
+    fn dst(&self) -> Option<usize> {
+        match self {
+            Opcode::Add(ref add_params) => Some(add_params.dst),
+            Opcode::Mult(ref mult_params) => Some(mult_params.dst),
+            Opcode::Input(ref input_params) => Some(input_params.dst),
+            Opcode::LessThan(ref compare_params) | Opcode::Equals(ref compare_params) => {
+                Some(compare_params.dst)
+            }
+            Opcode::Halt
+            | Opcode::Output(_)
+            | Opcode::JumpIfTrue(_)
+            | Opcode::JumpIfFalse(_)
+            | Opcode::AdjustBase(_) => None,
+        }
+    }
+}
+
+struct MultParams {
+    src1: usize,
+    src2: usize,
+    dst: usize,
+}
+
+struct AddParams {
+    src1: usize,
+    src2: usize,
+    dst: usize,
+}
+
+struct InputParams {
+    dst: usize,
+}
+
+struct OutputParams {
+    src: usize,
+}
+
+struct JumpParams {
+    cond: usize,
+    target: usize,
+}
+
+struct CompareParams {
+    src1: usize,
+    src2: usize,
+    dst: usize,
+}
+
+struct AdjustBaseParams {
+    src: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn step_back_restores_memory() {
+        let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        let mut intcode = Intcode::new(input.clone()).unwrap();
+        intcode.reset().record(10).run().unwrap();
+        assert_eq!(intcode.steps(), 3);
+        assert!(intcode.step_back());
+        assert!(intcode.step_back());
+        assert_eq!(intcode.pc(), 4);
+        assert_eq!(intcode.dump()[0], 1);
+        assert!(intcode.step_back());
+        assert_eq!(intcode.dump(), input);
+        assert!(!intcode.step_back());
+    }
+
+    #[test]
+    fn step_back_without_recording_does_nothing() {
+        let mut intcode = Intcode::new(vec![1, 0, 0, 0, 99]).unwrap();
+        intcode.reset().run().unwrap();
+        assert!(!intcode.step_back());
+        assert_eq!(intcode.dump(), vec![2, 0, 0, 0, 99]);
+    }
+
+    #[test]
+    fn rewind_to_last_write_of_address() {
+        let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().record(10).run().unwrap();
+        assert!(intcode.rewind_to_last_write(3));
+        assert_eq!(intcode.pc(), 0);
+        assert_eq!(intcode.dump()[3], 3);
+        assert!(!intcode.rewind_to_last_write(11));
+        assert_eq!(intcode.pc(), 0);
+    }
+
+    #[test]
+    fn goto_step_moves_both_ways() {
+        let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().record(10);
+        intcode.goto_step(2).unwrap();
+        assert_eq!(intcode.pc(), 8);
+        assert_eq!(intcode.read_output(), 3500);
+        intcode.goto_step(1).unwrap();
+        assert_eq!(intcode.read_output(), 1);
+        assert!(intcode.goto_step(5).is_err());
+    }
+
+    #[test]
+    fn goto_step_onto_halt() {
+        let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset();
+        assert_eq!(intcode.goto_step(3), Ok(Status::Halted));
+        assert_eq!(intcode.steps(), 3);
+        assert_eq!(intcode.goto_step(4), Err(IntcodeError::Halted { pc: 8 }));
+    }
+
+    #[test]
+    fn history_window_limits_rewind() {
+        let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().record(1).run().unwrap();
+        assert!(intcode.goto_step(1).is_err());
+        assert_eq!(intcode.steps(), 3);
+        intcode.goto_step(2).unwrap();
+        assert_eq!(intcode.pc(), 8);
+    }
+
+    #[test]
+    fn checked_overflow_reports_pc() {
+        let input = vec![1101, 1, 1, 0, 1101, i64::MAX, 1, 0, 99];
+        let mut intcode = Intcode::new(input).unwrap();
+        assert_eq!(intcode.reset().run(), Err(IntcodeError::Overflow { pc: 4 }));
+        assert_eq!(intcode.pc(), 4);
+        assert_eq!(intcode.read_output(), 2);
+    }
+
+    #[test]
+    fn wrapping_overflow_wraps() {
+        let input = vec![1102, i64::MAX, 2, 0, 99];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().overflow(Overflow::Wrapping).run().unwrap();
+        assert_eq!(intcode.read_output(), -2);
+    }
+
+    #[test]
+    fn big_int_cells_do_not_overflow() {
+        let input: Vec<BigInt> = vec![1102, 1i64 << 40, 1 << 40, 0, 99]
+            .into_iter()
+            .map(BigInt::from)
+            .collect();
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().run().unwrap();
+        assert_eq!(intcode.read_output(), BigInt::from(1) << 80);
+    }
+
+    #[test]
+    fn malformed_programs_report_errors() {
+        let cases = vec![
+            (vec![1, 0, 0, 0, 42], IntcodeError::UnknownOpcode { pc: 4 }),
+            (vec![301, 0, 0, 0, 99], IntcodeError::InvalidMode { pc: 0 }),
+            (
+                vec![10001, 0, 0, 0, 99],
+                IntcodeError::InvalidMode { pc: 0 },
+            ),
+            (
+                vec![1, 0, 0, MEMORY_LIMIT as i64, 99],
+                IntcodeError::InvalidAddress { pc: 0 },
+            ),
+            (
+                vec![1, -1, 0, 0, 99],
+                IntcodeError::InvalidAddress { pc: 0 },
+            ),
+            (
+                vec![109, -1, 204, 0, 99],
+                IntcodeError::InvalidAddress { pc: 2 },
+            ),
+            (vec![1101, 0, 0, 0], IntcodeError::UnknownOpcode { pc: 4 }),
+            (
+                vec![109, i64::MAX, 109, 1, 99],
+                IntcodeError::Overflow { pc: 2 },
+            ),
+        ];
+        for (input, error) in cases {
+            let mut intcode = Intcode::new(input).unwrap();
+            assert_eq!(intcode.reset().run(), Err(error));
+        }
+    }
+
+    #[test]
+    fn inputs_and_outputs_are_queued() {
+        let input = vec![3, 0, 4, 0, 3, 0, 4, 0, 99];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().push_input(7).push_input(8).run().unwrap();
+        assert_eq!(intcode.take_output(), Some(7));
+        assert_eq!(intcode.take_outputs(), vec![8]);
+        assert_eq!(intcode.take_output(), None);
+    }
+
+    #[test]
+    fn step_back_restores_inputs_and_outputs() {
+        let input = vec![3, 0, 4, 0, 99];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().record(10).push_input(5).run().unwrap();
+        assert_eq!(intcode.take_output(), Some(5));
+        intcode.goto_step(0).unwrap();
+        assert_eq!(intcode.take_output(), None);
+        intcode.run().unwrap();
+        assert_eq!(intcode.take_outputs(), vec![5]);
+    }
+
+    #[test]
+    fn step_budget_stops_and_resumes() {
+        let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().limit_steps(2);
+        assert_eq!(intcode.run(), Ok(Status::OutOfSteps { pc: 8, steps: 2 }));
+        assert_eq!(intcode.run(), Ok(Status::OutOfSteps { pc: 8, steps: 2 }));
+        intcode.grant_steps(1);
+        assert_eq!(intcode.run(), Ok(Status::Halted));
+        assert_eq!(intcode.read_output(), 3500);
+    }
+
+    #[test]
+    fn deadline_stops_and_resumes() {
+        let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().deadline(Instant::now());
+        assert_eq!(
+            intcode.run(),
+            Ok(Status::DeadlineExceeded { pc: 0, steps: 0 })
+        );
+        intcode.time_limit(Duration::from_secs(60));
+        assert_eq!(intcode.run(), Ok(Status::Halted));
+    }
+
+    #[test]
+    fn goto_step_respects_budget() {
+        let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().limit_steps(1);
+        assert_eq!(
+            intcode.goto_step(2),
+            Ok(Status::OutOfSteps { pc: 4, steps: 1 })
+        );
+    }
+
+    #[test]
+    fn step_back_refunds_budget() {
+        let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().record(10).limit_steps(2);
+        assert_eq!(intcode.run(), Ok(Status::OutOfSteps { pc: 8, steps: 2 }));
+        assert_eq!(intcode.goto_step(0), Ok(Status::Running));
+        assert_eq!(intcode.run(), Ok(Status::OutOfSteps { pc: 8, steps: 2 }));
+        assert!(intcode.step_back());
+        assert_eq!(intcode.run(), Ok(Status::OutOfSteps { pc: 8, steps: 2 }));
+    }
+
+    #[test]
+    fn missing_input_pauses_machine() {
+        let input = vec![4, 0, 3, 0, 4, 0, 99];
+        let mut intcode = Intcode::new(input).unwrap();
+        assert_eq!(intcode.reset().run(), Ok(Status::AwaitingInput { pc: 2 }));
+        assert_eq!(intcode.take_outputs(), vec![4]);
+        assert_eq!(intcode.push_input(9).run(), Ok(Status::Halted));
+        assert_eq!(intcode.take_outputs(), vec![9]);
+    }
+
+    #[test]
+    fn outputs_are_grouped_into_tuples() {
+        let input = vec![104, 1, 104, 2, 104, 3, 104, 4, 99];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().run().unwrap();
+        assert_eq!(intcode.take_tuples(3), vec![vec![1, 2, 3]]);
+        assert_eq!(intcode.take_outputs(), vec![4]);
+    }
+
+    #[test]
+    fn relative_mode_uses_relative_base() {
+        let input = vec![109, 7, 204, -1, 21101, 2, 3, 2, 99];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().run().unwrap();
+        assert_eq!(intcode.relative_base(), 7);
+        assert_eq!(intcode.take_outputs(), vec![3]);
+        assert_eq!(intcode.dump()[9], 5);
+    }
+
+    #[test]
+    fn memory_grows_beyond_program() {
+        let input = vec![1101, 2, 3, 1000, 4, 1000, 4, 2000, 99];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().run().unwrap();
+        assert_eq!(intcode.take_outputs(), vec![5, 0]);
+        assert_eq!(intcode.dump().len(), 2001);
+        intcode.reset().memory_limit(1500);
+        assert_eq!(intcode.run(), Err(IntcodeError::InvalidAddress { pc: 6 }));
+        assert_eq!(intcode.take_outputs(), vec![5]);
+    }
+
+    #[test]
+    fn step_back_restores_relative_base() {
+        let input = vec![109, 5, 109, -2, 99];
+        let mut intcode = Intcode::new(input).unwrap();
+        intcode.reset().record(10).run().unwrap();
+        assert_eq!(intcode.relative_base(), 3);
+        intcode.goto_step(1).unwrap();
+        assert_eq!(intcode.relative_base(), 5);
+        intcode.goto_step(0).unwrap();
+        assert_eq!(intcode.relative_base(), 0);
+    }
+
+    #[test]
+    fn parse_program_ignores_whitespace() {
+        assert_eq!(parse_program("1,0, 0,3,99\n"), vec![1, 0, 0, 3, 99]);
+    }
+}
@@ -0,0 +1,143 @@
+/// The 4x6 block letters used by several puzzles, with `#` for lit pixels.
+/// Each letter is followed by one blank column.
+const LETTERS: [(char, [&str; 6]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const HEIGHT: usize = 6;
+const WIDTH: usize = 5;
+
+/// Reads the block letters in `pixels`, given as rows of lit pixels. The
+/// letters may start anywhere in the first few columns. Returns `None` if
+/// the image is not 6 rows high or contains an unknown letter.
+pub fn read_letters(pixels: &[Vec<bool>]) -> Option<String> {
+    if pixels.len() != HEIGHT {
+        return None;
+    }
+    let columns = pixels.iter().map(|row| row.len()).max()?;
+    (0..WIDTH).find_map(|offset| read_from(pixels, offset, columns))
+}
+
+fn read_from(pixels: &[Vec<bool>], offset: usize, columns: usize) -> Option<String> {
+    if pixels.iter().any(|row| row.iter().take(offset).any(|&p| p)) {
+        return None;
+    }
+    let mut text = String::new();
+    let mut start = offset;
+    while start < columns {
+        let glyph: Vec<String> = pixels
+            .iter()
+            .map(|row| {
+                (start..start + WIDTH - 1)
+                    .map(|x| if row.get(x) == Some(&true) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let spacing_is_blank = pixels
+            .iter()
+            .all(|row| row.get(start + WIDTH - 1) != Some(&true));
+        if glyph.iter().all(|line| !line.contains('#')) && spacing_is_blank {
+            start += WIDTH;
+            continue;
+        }
+        let letter = LETTERS
+            .iter()
+            .find(|(_, lines)| lines.iter().zip(glyph.iter()).all(|(a, b)| a == b))
+            .filter(|_| spacing_is_blank)?;
+        text.push(letter.0);
+        start += WIDTH;
+    }
+    Some(text)
+}
+
+/// Draws `text` in block letters, the inverse of `read_letters`.
+pub fn draw_letters(text: &str) -> Option<Vec<Vec<bool>>> {
+    let mut pixels = vec![vec![]; HEIGHT];
+    for c in text.chars() {
+        let (_, lines) = LETTERS.iter().find(|(letter, _)| *letter == c)?;
+        for (row, line) in pixels.iter_mut().zip(lines.iter()) {
+            row.extend(line.chars().map(|p| p == '#'));
+            row.push(false);
+        }
+    }
+    Some(pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(lines: &[&str]) -> Vec<Vec<bool>> {
+        lines
+            .iter()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn reads_letters() {
+        let pixels = parse(&[
+            "#..#..###.",
+            "#..#...#..",
+            "####...#..",
+            "#..#...#..",
+            "#..#...#..",
+            "#..#..###.",
+        ]);
+        assert_eq!(read_letters(&pixels), Some(String::from("HI")));
+    }
+
+    #[test]
+    fn last_spacing_column_is_optional() {
+        let pixels = parse(&[
+            "####.#...",
+            "#....#...",
+            "###..#...",
+            "#....#...",
+            "#....#...",
+            "#....####",
+        ]);
+        assert_eq!(read_letters(&pixels), Some(String::from("FL")));
+    }
+
+    #[test]
+    fn reads_drawn_letters() {
+        let text = "ABCEFGHIJKLOPRSUZ";
+        let pixels = draw_letters(text).unwrap();
+        assert_eq!(read_letters(&pixels), Some(String::from(text)));
+    }
+
+    #[test]
+    fn reads_letters_with_leading_blank_columns() {
+        let mut pixels = draw_letters("IL").unwrap();
+        for row in pixels.iter_mut() {
+            row.insert(0, false);
+            row.insert(0, false);
+        }
+        assert_eq!(read_letters(&pixels), Some(String::from("IL")));
+    }
+
+    #[test]
+    fn rejects_unknown_shapes() {
+        let pixels = parse(&["####", "####", "####", "####", "####", "####"]);
+        assert_eq!(read_letters(&pixels), None);
+        assert_eq!(read_letters(&parse(&["#..#"])), None);
+        assert_eq!(draw_letters("Q"), None);
+    }
+}
@@ -0,0 +1,177 @@
+use std::ops::{Add, Mul, Sub};
+
+/// A position or offset on an integer grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    /// Distance from the origin when moving along the grid.
+    pub fn manhattan(&self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    /// The shortest offset pointing in the same direction, found by dividing
+    /// both coordinates by their greatest common divisor. The origin stays
+    /// the origin.
+    pub fn reduced(&self) -> Point {
+        match gcd(self.x, self.y) {
+            0 => *self,
+            divisor => Point::new(self.x / divisor, self.y / divisor),
+        }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+/// One of the four grid directions, with y growing downwards as on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All four directions, clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    /// The step of length one in this direction.
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+}
+
+/// Greatest common divisor, always non-negative.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        let rest = a % b;
+        a = b;
+        b = rest;
+    }
+    a
+}
+
+/// Least common multiple, always non-negative.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    match gcd(a, b) {
+        0 => 0,
+        divisor => (a / divisor * b).abs(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_of_signed_values() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-4, 6), 2);
+        assert_eq!(gcd(0, -5), 5);
+        assert_eq!(gcd(0, 0), 0);
+    }
+
+    #[test]
+    fn lcm_of_signed_values() {
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-3, 5), 15);
+        assert_eq!(lcm(0, 7), 0);
+    }
+
+    #[test]
+    fn reduced_keeps_direction() {
+        assert_eq!(Point::new(4, -6).reduced(), Point::new(2, -3));
+        assert_eq!(Point::new(0, 7).reduced(), Point::new(0, 1));
+        assert_eq!(Point::new(0, 0).reduced(), Point::new(0, 0));
+    }
+
+    #[test]
+    fn turns_go_round() {
+        let mut direction = Direction::Up;
+        for expected in [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ]
+        .iter()
+        {
+            direction = direction.turn_right();
+            assert_eq!(direction, *expected);
+        }
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+        assert_eq!(Direction::Up.offset() * 3, Point::new(0, -3));
+    }
+
+    #[test]
+    fn arithmetic_and_distance() {
+        let offset = Point::new(3, -4) - Point::new(1, 1);
+        assert_eq!(offset, Point::new(2, -5));
+        assert_eq!(offset.manhattan(), 7);
+        assert_eq!(offset + Point::new(-2, 5), Point::new(0, 0));
+    }
+}
@@ -107,7 +107,7 @@ impl Board {
     }
 }
 
-fn play_bingo(boards: &mut Vec<Board>, random_nums: &[i64]) -> i64 {
+fn play_bingo(boards: &mut [Board], random_nums: &[i64]) -> i64 {
     for &rn in random_nums {
         mark_numbers(boards, rn);
         if let Some(winning_board) = find_winning_board(boards) {
@@ -130,7 +130,7 @@ fn play_bingo_until_last_board(boards: &mut Vec<Board>, random_nums: &[i64]) ->
     0
 }
 
-fn mark_numbers(boards: &mut Vec<Board>, num: i64) {
+fn mark_numbers(boards: &mut [Board], num: i64) {
     boards.iter_mut().for_each(|b| b.mark_number(num));
 }
 
@@ -137,7 +137,7 @@ mod part_2 {
         }
 
         pub fn count(&self) -> i64 {
-            self.fish.iter().sum::<i64>() as i64
+            self.fish.iter().sum::<i64>()
         }
     }
 